@@ -4,7 +4,7 @@ use {
 	serde::{Deserialize, Serialize},
 };
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(
 	feature = "runtime",
 	derive(Encode, Decode, Serialize, Deserialize, MaxEncodedLen, TypeInfo)
@@ -15,7 +15,7 @@ pub enum FungibleAssetLocation {
 	External { network: NetworkId, child: Option<Para> },
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(
 	feature = "runtime",
 	derive(Encode, Decode, Serialize, Deserialize, MaxEncodedLen, TypeInfo)
@@ -26,7 +26,7 @@ pub struct Para {
 	index: u32,
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 #[cfg_attr(
 	feature = "runtime",
 	derive(Encode, Decode, Serialize, Deserialize, MaxEncodedLen, TypeInfo)
@@ -6,8 +6,12 @@ use wasm_bindgen::prelude::*;
 #[derive(Debug, Default, Clone, Eq, Copy, PartialEq)]
 #[repr(C)]
 pub struct PaymentId {
-	prefix: [u8; 2],
+	/// The position of the payment among the ones created in its block, which
+	/// tells apart payments created by the same extrinsic (e.g. in a batch)
+	payment_index: u16,
+	/// The index of the extrinsic that created the payment
 	index: u16,
+	/// The block the payment was created in
 	block: u32,
 }
 
@@ -34,6 +38,11 @@ impl PaymentId {
 		self.index as u32
 	}
 
+	#[cfg_attr(feature = "js", wasm_bindgen(getter = paymentIndex))]
+	pub fn payment_index(&self) -> u16 {
+		self.payment_index
+	}
+
 	#[cfg(all(feature = "nightly", feature = "alloc"))]
 	pub fn encode(&self, pretty: bool) -> alloc::string::String {
 		if pretty {
@@ -63,27 +72,41 @@ impl From<PaymentId> for u64 {
 impl From<u64> for PaymentId {
 	fn from(value: u64) -> Self {
 		let val = value.to_le_bytes();
+		let payment_index = u16::from_le_bytes(val[..2].try_into().unwrap());
 		let index = u16::from_le_bytes(val[2..4].try_into().unwrap());
 		let block = u32::from_le_bytes(val[4..].try_into().unwrap());
 		PaymentId {
-			prefix: [val[0], val[1]],
+			payment_index,
 			block,
 			index,
 		}
 	}
 }
 
-impl From<(u32, u32, &[u8])> for PaymentId {
-	fn from((block, idx, extra): (u32, u32, &[u8])) -> Self {
-		debug_assert!(extra.len() >= 2);
+/// Builds the id from the block, the extrinsic index and the index of the
+/// payment within the block
+impl From<(u32, u16, u16)> for PaymentId {
+	fn from((block, idx, payment_index): (u32, u16, u16)) -> Self {
 		PaymentId {
-			prefix: extra[..2].try_into().expect("at least 2 bytes"),
+			payment_index,
 			block,
-			index: idx as u16,
+			index: idx,
 		}
 	}
 }
 
+/// Builds the id from the block, the extrinsic index and the (little endian)
+/// index of the payment within the block, failing when the extrinsic index
+/// doesn't fit in the id instead of truncating it.
+impl TryFrom<(u32, u32, &[u8])> for PaymentId {
+	type Error = ();
+
+	fn try_from((block, idx, extra): (u32, u32, &[u8])) -> Result<Self, Self::Error> {
+		let payment_index = u16::from_le_bytes(extra.get(..2).ok_or(())?.try_into().map_err(|_| ())?);
+		Ok((block, u16::try_from(idx).map_err(|_| ())?, payment_index).into())
+	}
+}
+
 impl AsRef<[u8]> for PaymentId {
 	fn as_ref(&self) -> &[u8] {
 		debug_assert_eq!(8, core::mem::size_of::<Self>());
@@ -177,7 +200,23 @@ mod tests {
 	#[test]
 	fn payment_id_u64() {
 		let id: PaymentId = u64::MAX.into();
-		assert_eq!(id, (u32::MAX, u32::MAX, &[0xFF, 0xFF][..]).into());
+		assert_eq!(id, (u32::MAX, u16::MAX, u16::MAX).into());
+		assert_eq!(id, (u32::MAX, u16::MAX as u32, &[0xFF, 0xFF][..]).try_into().unwrap());
+	}
+
+	#[test]
+	fn payment_id_rejects_extrinsic_index_overflow() {
+		assert!(PaymentId::try_from((1, u16::MAX as u32 + 1, &[0, 0][..])).is_err());
+		assert!(PaymentId::try_from((1, 1, &[0][..])).is_err());
+	}
+
+	#[test]
+	fn payment_id_fields() {
+		let id: PaymentId = (1_234_567, 5, 2).into();
+		assert_eq!(id.block_number(), 1_234_567);
+		assert_eq!(id.extrinsic_index(), 5);
+		assert_eq!(id.payment_index(), 2);
+		assert_eq!(PaymentId::from_number(id.to_number()), id);
 	}
 
 	#[test]
@@ -187,7 +226,7 @@ mod tests {
 		assert_eq!(format!("{id:#}"), "jpXCZ-edGfVQ");
 
 		const TEST_ID: &str = "LbNvS-NtVQs";
-		let id: PaymentId = (1_234_567, 5, &[2, 5][..]).into();
+		let id: PaymentId = (1_234_567, 5, u16::from_le_bytes([2, 5])).into();
 		assert_eq!(format!("{id:#}"), TEST_ID);
 		assert_eq!(id, TEST_ID.parse().unwrap());
 	}
@@ -3,9 +3,13 @@ use super::*;
 
 use frame_benchmarking::v2::*;
 
-use frame_support::traits::fungible::Mutate;
+use frame_support::traits::{
+	fungible::Mutate,
+	fungibles::{Create as AssetsCreate, Mutate as _},
+};
 use frame_system::RawOrigin;
-use sp_runtime::SaturatedConversion;
+use pallet_communities::BenchmarkHelper;
+use sp_runtime::{Perbill, SaturatedConversion};
 
 type RuntimeEventFor<T> = <T as Config>::RuntimeEvent;
 
@@ -45,6 +49,71 @@ fn setup_collection<T: Config>() -> Result<(), BenchmarkError> {
 	Ok(())
 }
 
+fn setup_community<T: Config>(community_id: CommunityIdOf<T>) -> Result<(), BenchmarkError>
+where
+	NativeBalanceOf<T>: From<u64>,
+{
+	let admin: AccountIdOf<T> = frame_benchmarking::account("founder", 0, 0);
+	setup_account::<T>(&admin)?;
+	Pallet::<T>::register(
+		RawOrigin::Root.into(),
+		community_id,
+		BoundedVec::truncate_from(b"Test Community".into()),
+		T::Lookup::unlookup(admin),
+		None,
+		None,
+		Default::default(),
+	)?;
+
+	Ok(())
+}
+
+/// Creates membership `1` priced in a newly created asset, and funds the buyer
+/// with enough of it to buy the membership.
+fn setup_membership_for_sale<T: Config>(
+	buyer: &AccountIdOf<T>,
+) -> Result<(AssetIdOf<T>, AssetBalanceOf<T>), BenchmarkError>
+where
+	AssetIdOf<T>: From<u32>,
+	AssetBalanceOf<T>: From<u64>,
+	<T as Config>::MembershipId: From<u32>,
+{
+	let asset: AssetIdOf<T> = 1u32.into();
+	let price: AssetBalanceOf<T> = 100u64.into();
+	T::Assets::create(asset.clone(), T::MembershipsManagerOwner::get(), true, 1u64.into())?;
+	T::Assets::mint_into(asset.clone(), buyer, 1_000u64.into())?;
+
+	Pallet::<T>::create_membership_tiers(
+		RawOrigin::Root.into(),
+		1u32.into(),
+		BoundedVec::truncate_from(vec![MembershipTier {
+			amount: 1,
+			rank: 1.into(),
+			tank_config: TankConfig::default(),
+			metadata: BoundedVec::new(),
+			price: (asset.clone(), price),
+			maybe_expiration: None,
+		}]),
+	)?;
+
+	Ok((asset, price))
+}
+
+/// Makes membership `1` a membership of the community, and funds the
+/// community account so it can pay for gas
+fn setup_member<T: Config>(community_id: CommunityIdOf<T>) -> Result<(), BenchmarkError>
+where
+	NativeBalanceOf<T>: From<u64>,
+	AssetIdOf<T>: From<u32>,
+	AssetBalanceOf<T>: From<u64>,
+	<T as Config>::MembershipId: From<u32>,
+{
+	let member: AccountIdOf<T> = frame_benchmarking::account("member", 0, 0);
+	setup_membership_for_sale::<T>(&member)?;
+	Pallet::<T>::buy_membership(RawOrigin::Signed(member).into(), community_id, 1u32.into())?;
+	setup_account::<T>(&pallet_communities::Pallet::<T>::community_account(&community_id))
+}
+
 #[benchmarks(
 where
 	RuntimeEventFor<T>: From<pallet_communities::Event<T>>,
@@ -52,18 +121,29 @@ where
 	BlockNumberFor<T>: From<u32>,
 	CommunityIdOf<T>: From<u16>,
 	<T as Config>::MembershipId: From<u32>,
+	AssetIdOf<T>: From<u32>,
+	AssetBalanceOf<T>: From<u64>,
 )]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn register() -> Result<(), BenchmarkError> {
+	fn register(f: Linear<0, MAX_FOUNDERS>) -> Result<(), BenchmarkError> {
 		// setup code
+		setup_collection::<T>()?;
 		let first_member: AccountIdOf<T> = frame_benchmarking::account("founder", 0, 0);
 		setup_account::<T>(&first_member)?;
 
 		let community_id: CommunityIdOf<T> = 1.into();
 		let first_admin = T::Lookup::unlookup(first_member.clone());
+		let founders = BoundedVec::truncate_from(
+			(0..f)
+				.map(|i| {
+					let who: AccountIdOf<T> = frame_benchmarking::account("founder", i + 1, 0);
+					(T::Lookup::unlookup(who), (i + 1).into(), GenericRank::from(1))
+				})
+				.collect(),
+		);
 
 		#[extrinsic_call]
 		_(
@@ -73,6 +153,7 @@ mod benchmarks {
 			first_admin,
 			None,
 			None,
+			founders,
 		);
 
 		// verification code
@@ -136,6 +217,350 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn create_membership_tiers(q: Linear<1, MAX_TIERED_MEMBERSHIPS>) -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+
+		let price = (1u32.into(), 10u64.into());
+		let tier = MembershipTier {
+			amount: q.saturated_into(),
+			rank: 1.into(),
+			tank_config: TankConfig {
+				capacity: Some(block_weight::<T>()),
+				periodicity: Some((7 * DAYS).into()),
+			},
+			metadata: BoundedVec::truncate_from(vec![0u8; 256]),
+			price: price.clone(),
+			maybe_expiration: Some(u32::MAX.into()),
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 100u32.into(), BoundedVec::truncate_from(vec![tier]));
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipTierCreated {
+				starting_at: 100u32.into(),
+				amount: q,
+				rank: 1.into(),
+				price,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn buy_membership() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id: CommunityIdOf<T> = 1.into();
+		setup_community::<T>(community_id)?;
+
+		let buyer: AccountIdOf<T> = frame_benchmarking::account("buyer", 0, 0);
+		let (asset, price) = setup_membership_for_sale::<T>(&buyer)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), community_id, 1u32.into());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipBought {
+				community_id,
+				membership_id: 1u32.into(),
+				who: buyer,
+				price: (asset, price),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_renewal_terms() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id = <T as pallet_communities::Config>::BenchmarkHelper::community_id();
+		setup_community::<T>(community_id)?;
+
+		let origin = <T as pallet_communities::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let terms = RenewalTerms {
+			price: (1u32.into(), 50u64.into()),
+			period: (30 * DAYS).into(),
+		};
+
+		#[extrinsic_call]
+		_(origin.into_caller(), Some(terms.clone()));
+
+		// verification code
+		assert_eq!(MembershipRenewalTerms::<T>::get(community_id), Some(terms));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn renew_membership() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id: CommunityIdOf<T> = 1.into();
+		setup_community::<T>(community_id)?;
+
+		let member: AccountIdOf<T> = frame_benchmarking::account("buyer", 0, 0);
+		let (asset, _) = setup_membership_for_sale::<T>(&member)?;
+		Pallet::<T>::buy_membership(RawOrigin::Signed(member.clone()).into(), community_id, 1u32.into())?;
+
+		let expiration: BlockNumberFor<T> = DAYS.into();
+		T::CreateMemberships::set_typed_attribute(&community_id, &1u32.into(), &b"membership_expiration", &expiration)?;
+		MembershipRenewalTerms::<T>::insert(
+			community_id,
+			RenewalTerms {
+				price: (asset, 50u64.into()),
+				period: (30 * DAYS).into(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(member), community_id, 1u32.into());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipRenewed {
+				community_id,
+				membership_id: 1u32.into(),
+				expiration: (31 * DAYS).into(),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_minting() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			MAX_MINTING_JOB_MEMBERSHIPS,
+			100u32.into(),
+			300_000_000_000u64.into(),
+			TankConfig {
+				capacity: Some(block_weight::<T>()),
+				periodicity: Some((7 * DAYS).into()),
+			},
+			Some(u32::MAX.into()),
+		);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MintingJobScheduled {
+				job_id: 0,
+				starting_at: 100u32.into(),
+				amount: MAX_MINTING_JOB_MEMBERSHIPS,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_minting() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		Pallet::<T>::schedule_minting(
+			RawOrigin::Root.into(),
+			MAX_MINTING_JOB_MEMBERSHIPS,
+			100u32.into(),
+			300_000_000_000u64.into(),
+			TankConfig::default(),
+			None,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 0);
+
+		// verification code
+		assert_has_event::<T>(Event::<T>::MintingJobCancelled { job_id: 0, minted: 0 }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_track_template() -> Result<(), BenchmarkError> {
+		// setup code
+		let track_info = TrackInfo {
+			name: str_array("template"),
+			max_deciding: 1,
+			decision_deposit: 0u8.into(),
+			prepare_period: 1u8.into(),
+			decision_period: 100u8.into(),
+			confirm_period: 1u8.into(),
+			min_enactment_period: 1u8.into(),
+			min_approval: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(50),
+				ceil: Perbill::from_percent(100),
+			},
+			min_support: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(0),
+				ceil: Perbill::from_percent(50),
+			},
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 1, Some(track_info));
+
+		// verification code
+		assert_has_event::<T>(Event::<T>::TrackTemplateSet { id: 1 }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn recall_memberships(q: Linear<1, 1024>) -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		Pallet::<T>::create_memberships(
+			RawOrigin::Root.into(),
+			q.saturated_into(),
+			100u32.into(),
+			300_000_000_000u64.into(),
+			TankConfig {
+				capacity: Some(block_weight::<T>()),
+				periodicity: Some((7 * DAYS).into()),
+			},
+			Some(u32::MAX.into()),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 100u32.into(), q.saturated_into());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipsRecalled {
+				starting_at: 100u32.into(),
+				amount: q,
+				recalled: q,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reprice_memberships(q: Linear<1, 1024>) -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		Pallet::<T>::create_memberships(
+			RawOrigin::Root.into(),
+			q.saturated_into(),
+			100u32.into(),
+			300_000_000_000u64.into(),
+			TankConfig::default(),
+			None,
+		)?;
+		let price = MembershipPrice::Asset(1u32.into(), 100u64.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 100u32.into(), q.saturated_into(), price.clone());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MembershipsRepriced {
+				starting_at: 100u32.into(),
+				amount: q,
+				repriced: q,
+				price,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fill_member_gas_tank() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id = <T as pallet_communities::Config>::BenchmarkHelper::community_id();
+		setup_community::<T>(community_id)?;
+		setup_member::<T>(community_id)?;
+
+		let origin = <T as pallet_communities::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let capacity = Weight::from_parts(1_000_000, 0);
+
+		#[extrinsic_call]
+		_(origin.into_caller(), 1u32.into(), capacity);
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MemberGasTankFilled {
+				community_id,
+				membership_id: 1u32.into(),
+				capacity,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_member_gas_refills() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id = <T as pallet_communities::Config>::BenchmarkHelper::community_id();
+		setup_community::<T>(community_id)?;
+		setup_member::<T>(community_id)?;
+
+		let origin = <T as pallet_communities::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin.into_caller(),
+			1u32.into(),
+			Some((Weight::from_parts(1_000_000, 0), (7 * DAYS).into())),
+		);
+
+		// verification code
+		assert!(MemberGasRefills::<T>::contains_key(
+			<T as Config>::MembershipId::from(1u32),
+			community_id
+		));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn refill_member_gas_tank() -> Result<(), BenchmarkError> {
+		// setup code
+		setup_collection::<T>()?;
+		let community_id = <T as pallet_communities::Config>::BenchmarkHelper::community_id();
+		setup_community::<T>(community_id)?;
+		setup_member::<T>(community_id)?;
+
+		let origin = <T as pallet_communities::Config>::AdminOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let capacity = Weight::from_parts(1_000_000, 0);
+		Pallet::<T>::set_member_gas_refills(origin, 1u32.into(), Some((capacity, (7 * DAYS).into())))?;
+
+		let caller: AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), community_id, 1u32.into());
+
+		// verification code
+		assert_has_event::<T>(
+			Event::<T>::MemberGasTankFilled {
+				community_id,
+				membership_id: 1u32.into(),
+				capacity,
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		sp_io::TestExternalities::new(Default::default()),
@@ -10,44 +10,177 @@ pub(crate) mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
 use fc_traits_gas_tank::MakeTank;
+use fc_traits_memberships::{GenericRank, Manager, Rank};
 use fc_traits_tracks::MutateTracks;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		nonfungibles_v2::Mutate as ItemMutate,
-		nonfungibles_v2::{Create as CollectionCreate, Trading},
+		fungible::Mutate as BalancesMutate,
+		fungibles::Mutate as AssetsMutate,
+		nonfungibles_v2::{Create as CollectionCreate, Trading, Transfer},
+		nonfungibles_v2::{Inspect as _, Mutate as ItemMutate},
+		tokens::Preservation,
 		Incrementable, OriginTrait, RankedMembers,
 	},
+	weights::WeightToFee,
 };
 use frame_system::pallet_prelude::{BlockNumberFor, OriginFor};
 use pallet_communities::{
-	types::{AccountIdOf, CommunityIdOf, DecisionMethodFor, NativeBalanceOf, PalletsOriginOf, RuntimeOriginFor},
-	Origin as CommunityOrigin,
+	types::{
+		AccountIdOf, AssetBalanceOf, AssetIdOf, CommunityIdOf, DecisionMethodFor, MembershipIdOf, NativeBalanceOf,
+		PalletsOriginOf, RuntimeOriginFor,
+	},
+	MembershipExpiry, Origin as CommunityOrigin,
 };
 use pallet_nfts::CollectionConfig;
 use pallet_referenda::{TrackInfo, TracksInfo};
 use parity_scale_codec::Decode;
 use sp_runtime::{
-	str_array,
-	traits::{Get, StaticLookup},
+	morph_types, str_array,
+	traits::{Get, Saturating, StaticLookup, Zero},
+	Percent,
 };
 
-type TrackInfoOf<T> = TrackInfo<NativeBalanceOf<T>, BlockNumberFor<T>>;
+pub type TrackInfoOf<T> = TrackInfo<NativeBalanceOf<T>, BlockNumberFor<T>>;
+/// The deposit to register a community: the amount, who pays it and who
+/// receives it.
+pub type RegistrationDepositOf<T> = Option<(NativeBalanceOf<T>, AccountIdOf<T>, AccountIdOf<T>)>;
+pub type MembershipTierOf<T> = MembershipTier<Weight, BlockNumberFor<T>, AssetIdOf<T>, AssetBalanceOf<T>>;
+pub type MembershipTiersOf<T> = BoundedVec<MembershipTierOf<T>, ConstU32<8>>;
+pub type MintingJobOf<T> = MintingJob<<T as Config>::MembershipId, NativeBalanceOf<T>, Weight, BlockNumberFor<T>>;
+pub type RenewalTermsOf<T> = RenewalTerms<AssetIdOf<T>, AssetBalanceOf<T>, BlockNumberFor<T>>;
+pub type CommunityTrackOf<T> = CommunityTrack<TrackInfoOf<T>>;
+pub type GasRefillOf<T> = GasRefill<Weight, BlockNumberFor<T>>;
+pub type MembershipPriceOf<T> = MembershipPrice<NativeBalanceOf<T>, AssetIdOf<T>, AssetBalanceOf<T>>;
+pub type FoundersOf<T> = BoundedVec<
+	(
+		pallet_communities::AccountIdLookupOf<T>,
+		<T as Config>::MembershipId,
+		GenericRank,
+	),
+	ConstU32<MAX_FOUNDERS>,
+>;
+
+/// The ID of a track template.
+pub type TrackTemplateId = u16;
+
+/// The template used for communities that register without specifying a
+/// track.
+pub const DEFAULT_TRACK_TEMPLATE: TrackTemplateId = 0;
+
+morph_types! {
+	/// Maps the success of an origin that only yields a registration deposit,
+	/// so it can be used as [`RegisterOrigin`](Config::RegisterOrigin).
+	pub type WithoutAdminOrigin<Deposit, AdminOrigin>: Morph = |deposit: Deposit| -> (Deposit, Option<AdminOrigin>) {
+		(deposit, None)
+	};
+}
+
+/// The maximum amount of memberships a single minting job can create.
+pub const MAX_MINTING_JOB_MEMBERSHIPS: u32 = 100_000;
 
-#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+/// The maximum amount of memberships a minting job can create in one go.
+pub const MAX_MINTING_CHUNK: u32 = 1024;
+
+/// The maximum amount of memberships that can be created at once across all
+/// the tiers given to `create_membership_tiers`.
+pub const MAX_TIERED_MEMBERSHIPS: u32 = 512;
+
+/// The maximum amount of founding members a community can be registered
+/// with.
+pub const MAX_FOUNDERS: u32 = 16;
+
+#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
 pub struct TankConfig<Weight, BlockNumber> {
 	capacity: Option<Weight>,
 	periodicity: Option<BlockNumber>,
 }
 
+/// A set of memberships that share the same initial rank, gas tank, metadata
+/// and price. The rank is set as the `membership_member_rank` attribute of
+/// every membership so it gets copied over when the membership is assigned.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct MembershipTier<Weight, BlockNumber, AssetId, Balance> {
+	/// How many memberships of this tier to create
+	pub amount: u16,
+	/// The rank a member gets when assigned a membership of this tier
+	pub rank: GenericRank,
+	pub tank_config: TankConfig<Weight, BlockNumber>,
+	/// URI pointing to the metadata of the memberships of this tier
+	pub metadata: BoundedVec<u8, ConstU32<256>>,
+	/// The price of each membership, given in an asset
+	pub price: (AssetId, Balance),
+	pub maybe_expiration: Option<BlockNumber>,
+}
+
+/// A request to create a large amount of memberships that is processed in
+/// chunks, either when blocks have weight to spare or via `continue_minting`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct MintingJob<MembershipId, Balance, Weight, BlockNumber> {
+	/// The ID of the next membership to mint
+	pub next_id: MembershipId,
+	/// How many memberships are yet to be minted
+	pub remaining: u32,
+	/// How many memberships have been minted so far
+	pub minted: u32,
+	pub price: Balance,
+	pub tank_config: TankConfig<Weight, BlockNumber>,
+	pub maybe_expiration: Option<BlockNumber>,
+}
+
+/// The terms under which the members of a community can renew their
+/// memberships.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct RenewalTerms<AssetId, Balance, BlockNumber> {
+	/// The price of a renewal, given in an asset
+	pub price: (AssetId, Balance),
+	/// The amount of blocks a renewal extends a membership for
+	pub period: BlockNumber,
+}
+
+/// Refills of the gas tank of a member, paid by the community.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct GasRefill<Weight, BlockNumber> {
+	/// The capacity of the tank after each refill
+	pub capacity: Weight,
+	/// The amount of blocks between refills
+	pub period: BlockNumber,
+	/// The block from which the next refill can be claimed
+	pub next_at: BlockNumber,
+}
+
+/// The price of a membership in the manager collection.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum MembershipPrice<Balance, AssetId, AssetBalance> {
+	/// Sold in the market of the collection, for the native currency
+	Native(Balance),
+	/// Sold through [`buy_membership`](Pallet::buy_membership), for an
+	/// asset
+	Asset(AssetId, AssetBalance),
+}
+
+/// The governance track a community is registered with.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum CommunityTrack<TrackInfo> {
+	/// Use one of the track templates stored on chain
+	Template(TrackTemplateId),
+	/// Use a custom track
+	Custom(TrackInfo),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use parity_scale_codec::HasCompact;
+	use sp_std::vec::Vec;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	type CommunityName = BoundedVec<u8, ConstU32<25>>;
 
@@ -84,14 +217,26 @@ pub mod pallet {
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
+		/// Origin allowed to register communities. Besides the deposit to
+		/// pay (if any), it might yield an origin (e.g. a remote location) to
+		/// record as the admin of the community instead of `first_admin`.
 		type RegisterOrigin: EnsureOrigin<
 			OriginFor<Self>,
-			Success = Option<(NativeBalanceOf<Self>, AccountIdOf<Self>, AccountIdOf<Self>)>,
+			Success = (RegistrationDepositOf<Self>, Option<PalletsOriginOf<Self>>),
 		>;
 
 		type CreateMembershipsOrigin: EnsureOrigin<OriginFor<Self>>;
 
-		type MembershipId: Parameter + Decode + Incrementable + HasCompact;
+		/// Origin allowed to manage the track templates communities can be
+		/// registered with.
+		type TrackTemplatesOrigin: EnsureOrigin<OriginFor<Self>>;
+
+		/// The track templates set at genesis (or by
+		/// [`SeedTrackTemplates`](migrations::v1::SeedTrackTemplates)), which
+		/// should include the [default one](DEFAULT_TRACK_TEMPLATE).
+		type InitialTrackTemplates: Get<Vec<(TrackTemplateId, TrackInfoOf<Self>)>>;
+
+		type MembershipId: Parameter + Decode + Incrementable + HasCompact + MaxEncodedLen + Into<MembershipIdOf<Self>>;
 
 		type MembershipsManagerCollectionId: Get<CommunityIdOf<Self>>;
 
@@ -107,12 +252,101 @@ pub mod pallet {
 				NativeBalanceOf<Self>,
 				CollectionId = CommunityIdOf<Self>,
 				ItemId = <Self as Config>::MembershipId,
-			>;
+			> + Transfer<AccountIdOf<Self>>;
+
+		/// The share of the price of a membership bought with
+		/// [`buy_membership`](Pallet::buy_membership) that goes to the
+		/// community the membership is assigned in. The rest goes to the
+		/// [`MembershipsManagerOwner`](Config::MembershipsManagerOwner).
+		type CommunityRevenueShare: Get<Percent>;
+
+		/// Converts the gas a community gives to its members into the
+		/// amount (in the native currency) charged to the community account.
+		type GasToFee: WeightToFee<Balance = NativeBalanceOf<Self>>;
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		#[serde(skip)]
+		_config: core::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (id, track_info) in T::InitialTrackTemplates::get() {
+				Pallet::<T>::validate_track_info(&track_info).expect("initial track templates are valid; qed");
+				TrackTemplates::<T>::insert(id, track_info);
+			}
+		}
+	}
+
+	/// The price (in a given asset) of the memberships in the manager
+	/// collection that were created as part of a tier.
+	#[pallet::storage]
+	pub type MembershipPrices<T: Config> =
+		StorageMap<_, Blake2_128Concat, <T as Config>::MembershipId, (AssetIdOf<T>, AssetBalanceOf<T>)>;
+
+	/// Pending jobs to create memberships in the manager collection.
+	#[pallet::storage]
+	pub type MintingJobs<T: Config> = StorageMap<_, Blake2_128Concat, u32, MintingJobOf<T>>;
+
+	/// The ID of the next minting job.
+	#[pallet::storage]
+	pub type NextMintingJobId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The ID of the first minting job that might still be pending, from
+	/// which jobs are advanced on idle in the order they were scheduled.
+	#[pallet::storage]
+	pub type FirstPendingMintingJob<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The terms set by a community for its members to renew their
+	/// memberships.
+	#[pallet::storage]
+	pub type MembershipRenewalTerms<T: Config> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, RenewalTermsOf<T>>;
+
+	/// The refills of the gas tanks of their members that communities have
+	/// scheduled.
+	#[pallet::storage]
+	pub type MemberGasRefills<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		<T as Config>::MembershipId,
+		Blake2_128Concat,
+		CommunityIdOf<T>,
+		GasRefillOf<T>,
+	>;
+
+	/// The accounts paying for the gas burned from the tanks communities
+	/// filled for their members. Tanks without a payer are sponsored by the
+	/// treasury.
+	#[pallet::storage]
+	pub type GasTankPayers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		<T as Config>::MembershipId,
+		Blake2_128Concat,
+		CommunityIdOf<T>,
+		AccountIdOf<T>,
+	>;
+
+	/// The periodicity of the gas tank a membership was created with, kept
+	/// so the tank keeps it when filled by a community.
+	#[pallet::storage]
+	pub type GasTankPeriodicity<T: Config> =
+		StorageMap<_, Blake2_128Concat, <T as Config>::MembershipId, BlockNumberFor<T>>;
+
+	/// Track templates communities can be registered with. The name of a
+	/// template is replaced with the name of the community when the track is
+	/// created.
+	#[pallet::storage]
+	pub type TrackTemplates<T: Config> = StorageMap<_, Blake2_128Concat, TrackTemplateId, TrackInfoOf<T>>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -121,11 +355,95 @@ pub mod pallet {
 		/// The community with [`CommunityId`](pallet_communities::CommunityId)
 		/// has been created.
 		CommunityRegistered { id: T::CommunityId },
-		/// The
+		/// A batch of memberships has been created in the manager collection
 		MembershipsCreated {
 			starting_at: <T as Config>::MembershipId,
 			amount: u32,
 		},
+		/// A tier of memberships has been created in the manager collection
+		MembershipTierCreated {
+			starting_at: <T as Config>::MembershipId,
+			amount: u32,
+			rank: GenericRank,
+			price: (AssetIdOf<T>, AssetBalanceOf<T>),
+		},
+		/// A membership has been bought and assigned to the buyer in the
+		/// given community
+		MembershipBought {
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+			who: AccountIdOf<T>,
+			price: (AssetIdOf<T>, AssetBalanceOf<T>),
+		},
+		/// A job to create memberships has been scheduled
+		MintingJobScheduled {
+			job_id: u32,
+			starting_at: <T as Config>::MembershipId,
+			amount: u32,
+		},
+		/// A chunk of memberships of a minting job has been created
+		MintingJobProgressed { job_id: u32, minted: u32, remaining: u32 },
+		/// All the memberships of a minting job have been created
+		MintingJobFinished { job_id: u32, minted: u32 },
+		/// A minting job has been cancelled before all its memberships were
+		/// created
+		MintingJobCancelled { job_id: u32, minted: u32 },
+		/// A minting job couldn't continue on idle. It is kept for
+		/// `continue_minting` to resume, but no longer advanced on idle
+		MintingJobFailed {
+			job_id: u32,
+			remaining: u32,
+			error: DispatchError,
+		},
+		/// A community has set (or removed) the terms for its members to
+		/// renew their memberships
+		RenewalTermsSet {
+			community_id: CommunityIdOf<T>,
+			terms: Option<RenewalTermsOf<T>>,
+		},
+		/// A membership has been renewed until the given block
+		MembershipRenewed {
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+			expiration: BlockNumberFor<T>,
+		},
+		/// A track template has been set
+		TrackTemplateSet { id: TrackTemplateId },
+		/// A track template has been removed
+		TrackTemplateRemoved { id: TrackTemplateId },
+		/// The unsold memberships within a range have been burned
+		MembershipsRecalled {
+			starting_at: <T as Config>::MembershipId,
+			amount: u32,
+			recalled: u32,
+		},
+		/// The unsold memberships within a range have been repriced
+		MembershipsRepriced {
+			starting_at: <T as Config>::MembershipId,
+			amount: u32,
+			repriced: u32,
+			price: MembershipPriceOf<T>,
+		},
+		/// A community has filled the gas tank of one of its members
+		MemberGasTankFilled {
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+			capacity: Weight,
+		},
+		/// A community has scheduled (or stopped) the refills of the gas
+		/// tank of one of its members
+		MemberGasRefillsSet {
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+			refill: Option<GasRefillOf<T>>,
+		},
+		/// A founding member has been added to a newly registered community
+		FoundingMemberAdded {
+			community_id: CommunityIdOf<T>,
+			who: AccountIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+			rank: GenericRank,
+		},
 	}
 
 	// Errors inform users that something worked or went wrong.
@@ -135,8 +453,45 @@ pub mod pallet {
 		InvalidCommunityName,
 		/// It was not possible to register the community
 		CannotRegister,
-		/// The amount of memberships to create exceeds the limit of 1024
+		/// The amount of memberships to create exceeds the limit (1024 for a
+		/// single batch, [`MAX_TIERED_MEMBERSHIPS`] across tiers)
 		CreatingTooManyMemberships,
+		/// The membership doesn't exist or doesn't have a price in an asset
+		MembershipNotForSale,
+		/// There's no minting job with the given ID
+		MintingJobNotFound,
+		/// The community doesn't allow its memberships to be renewed
+		NoRenewalTerms,
+		/// The membership has no expiration, so there's nothing to renew
+		MembershipDoesNotExpire,
+		/// There's no track template with the given ID
+		TrackTemplateNotFound,
+		/// The track parameters would make it impossible (or unsafe) to
+		/// approve referenda
+		InvalidTrackInfo,
+		/// The membership doesn't belong to the community
+		UnknownMembership,
+		/// The community hasn't scheduled refills for the membership
+		NoGasRefills,
+		/// The next refill of the gas tank can't be claimed yet
+		GasRefillNotDue,
+		/// The membership has already been given away, or can't be minted
+		/// for the origin registering the community
+		MembershipNotAvailable,
+		/// Only the holder of the membership or the community can do this
+		NotMembershipHolder,
+		/// The community account can't afford the gas it gives to a member
+		InsufficientFundsForGas,
+		/// The range of memberships to recall or reprice exceeds the limit
+		/// (1024 at once)
+		TooManyMemberships,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::mint_on_idle(remaining_weight)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke
@@ -145,6 +500,21 @@ pub mod pallet {
 	// weight and must return a DispatchResult.
 	#[pallet::call(weight(<T as Config>::WeightInfo))]
 	impl<T: Config> Pallet<T> {
+		/// Registers a community along with its governance track and
+		/// memberships collection.
+		///
+		/// Founding members get the given membership from the manager
+		/// collection, assigned with the given rank. Memberships that don't
+		/// exist yet are only minted when the community is registered for
+		/// free; otherwise, the registrant pays the price of every membership,
+		/// which is split with the community like when
+		/// [buying](Pallet::buy_membership) a membership. Registering for free
+		/// (e.g. as Root) is approved by governance, so the founders'
+		/// memberships are taken without paying their price.
+		///
+		/// When the origin yields an admin origin of its own (like a sibling
+		/// chain registering through XCM), `first_admin` is ignored.
+		#[pallet::weight(<T as Config>::WeightInfo::register(founders.len() as u32))]
 		#[pallet::call_index(0)]
 		pub fn register(
 			origin: OriginFor<T>,
@@ -152,18 +522,23 @@ pub mod pallet {
 			name: CommunityName,
 			first_admin: pallet_communities::AccountIdLookupOf<T>,
 			maybe_decision_method: Option<DecisionMethodFor<T>>,
-			maybe_track_info: Option<TrackInfoOf<T>>,
-			// _maybe_first_member: Option<AccountIdLookupOf<T>>,
+			maybe_track: Option<CommunityTrackOf<T>>,
+			founders: FoundersOf<T>,
 		) -> DispatchResult {
-			let maybe_deposit = T::RegisterOrigin::ensure_origin(origin)?;
+			let (maybe_deposit, maybe_admin_origin) = T::RegisterOrigin::ensure_origin(origin)?;
 
 			let community_name = core::str::from_utf8(&name).map_err(|_| Error::<T>::InvalidCommunityName)?;
+			let track_info = Self::community_track_info(community_name, maybe_track)?;
+
+			let admin_origin: PalletsOriginOf<T> = match maybe_admin_origin {
+				Some(admin_origin) => admin_origin,
+				None => frame_system::Origin::<T>::Signed(T::Lookup::lookup(first_admin)?).into(),
+			};
 
-			let first_admin_account_id = T::Lookup::lookup(first_admin)?;
-			let admin_origin = frame_system::Origin::<T>::Signed(first_admin_account_id);
+			let maybe_payer = maybe_deposit.as_ref().map(|(_, payer, _)| payer.clone());
 
 			// Register first to check if community exists
-			pallet_communities::Pallet::<T>::register(&admin_origin.clone().into(), &community_id, maybe_deposit)?;
+			pallet_communities::Pallet::<T>::register(&admin_origin, &community_id, maybe_deposit)?;
 
 			if let Some(decision_method) = maybe_decision_method {
 				pallet_communities::Pallet::<T>::set_decision_method(
@@ -189,14 +564,15 @@ pub mod pallet {
 
 			// Create governance track for community
 			let community_origin: RuntimeOriginFor<T> = CommunityOrigin::<T>::new(community_id).into();
-			T::Tracks::insert(
-				community_id,
-				maybe_track_info.unwrap_or_else(|| Self::default_tack(community_name)),
-				community_origin.into_caller(),
-			)?;
+			T::Tracks::insert(community_id, track_info, community_origin.into_caller())?;
 			// Induct community at Kreivo Governance with rank 0
 			T::RankedCollective::induct(&community_account)?;
 
+			for (who, membership_id, rank) in founders {
+				let who = T::Lookup::lookup(who)?;
+				Self::do_add_founder(&community_id, &who, &membership_id, rank, maybe_payer.as_ref())?;
+			}
+
 			Self::deposit_event(Event::<T>::CommunityRegistered { id: community_id });
 			Ok(())
 		}
@@ -218,32 +594,7 @@ pub mod pallet {
 			let mut id = starting_at.clone();
 			let mut minted = 0u32;
 			for _ in 0..amount {
-				T::CreateMemberships::mint_into(
-					collection_id,
-					&id,
-					&T::MembershipsManagerOwner::get(),
-					&Default::default(),
-					true,
-				)?;
-
-				Self::do_set_gas_tank(&(*collection_id, id.clone()), &tank_config)?;
-
-				if let Some(expiration) = maybe_expiration {
-					T::CreateMemberships::set_typed_attribute(
-						collection_id,
-						&id,
-						&b"membership_expiration",
-						&expiration,
-					)?;
-				}
-
-				T::CreateMemberships::set_price(
-					&T::MembershipsManagerCollectionId::get(),
-					&id,
-					&T::MembershipsManagerOwner::get(),
-					Some(price),
-					None,
-				)?;
+				Self::do_create_membership_for_sale(collection_id, &id, price, &tank_config, maybe_expiration)?;
 				if let Some(next_id) = id.increment() {
 					id = next_id;
 					minted += 1;
@@ -269,9 +620,728 @@ pub mod pallet {
 			T::CreateMembershipsOrigin::ensure_origin(origin)?;
 			Self::do_set_gas_tank(&(community_id, membership_id), &config)
 		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::create_membership_tiers(Pallet::<T>::tiers_total(tiers)))]
+		#[pallet::call_index(3)]
+		pub fn create_membership_tiers(
+			origin: OriginFor<T>,
+			starting_at: <T as Config>::MembershipId,
+			tiers: MembershipTiersOf<T>,
+		) -> DispatchResult {
+			ensure!(
+				Self::tiers_total(&tiers) <= MAX_TIERED_MEMBERSHIPS,
+				Error::<T>::CreatingTooManyMemberships
+			);
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+			let mut id = starting_at;
+			for tier in tiers {
+				let tier_starting_at = id.clone();
+				let mut minted = 0u32;
+				for _ in 0..tier.amount {
+					Self::do_create_membership(collection_id, &id, &tier.tank_config, tier.maybe_expiration)?;
+
+					T::CreateMemberships::set_typed_attribute(
+						collection_id,
+						&id,
+						&b"membership_member_rank",
+						&tier.rank,
+					)?;
+					if !tier.metadata.is_empty() {
+						T::CreateMemberships::set_item_metadata(None, collection_id, &id, &tier.metadata)?;
+					}
+					MembershipPrices::<T>::insert(&id, tier.price.clone());
+
+					id = id.increment().ok_or(Error::<T>::CreatingTooManyMemberships)?;
+					minted += 1;
+				}
+
+				Self::deposit_event(Event::<T>::MembershipTierCreated {
+					starting_at: tier_starting_at,
+					amount: minted,
+					rank: tier.rank,
+					price: tier.price,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Buys a membership of the manager collection paying its price in
+		/// the asset it was set for, and assigns it to the buyer in the given
+		/// community. The revenue of the sale is split between the community
+		/// and the owner of the manager collection.
+		#[pallet::call_index(4)]
+		pub fn buy_membership(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				pallet_communities::Pallet::<T>::community_exists(&community_id),
+				pallet_communities::Error::<T>::CommunityDoesNotExist
+			);
+
+			let (asset, price) = MembershipPrices::<T>::take(&membership_id).ok_or(Error::<T>::MembershipNotForSale)?;
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			Self::do_pay_revenue(&who, &community_id, MembershipPrice::Asset(asset.clone(), price))?;
+
+			// The membership is handed to the community first, so it can be
+			// assigned as if the community had given it away itself
+			T::CreateMemberships::transfer(
+				&T::MembershipsManagerCollectionId::get(),
+				&membership_id,
+				&community_account,
+			)?;
+			let m: MembershipIdOf<T> = membership_id.clone().into();
+			T::MemberMgmt::assign(&community_id, &m, &who)?;
+			pallet_communities::Pallet::<T>::track_membership_expiry(&community_id, &m);
+
+			Self::deposit_event(Event::<T>::MembershipBought {
+				community_id,
+				membership_id,
+				who,
+				price: (asset, price),
+			});
+			Ok(())
+		}
+
+		/// Sets the terms under which the members of the community can renew
+		/// their memberships. Passing `None` disables renewals.
+		#[pallet::call_index(5)]
+		pub fn set_renewal_terms(origin: OriginFor<T>, maybe_terms: Option<RenewalTermsOf<T>>) -> DispatchResult {
+			let community_id = <T as pallet_communities::Config>::AdminOrigin::ensure_origin(origin)?;
+
+			MembershipRenewalTerms::<T>::set(community_id, maybe_terms.clone());
+
+			Self::deposit_event(Event::<T>::RenewalTermsSet {
+				community_id,
+				terms: maybe_terms,
+			});
+			Ok(())
+		}
+
+		/// Extends the expiration of a membership by the period set in the
+		/// community's renewal terms, paying the price of the renewal. If the
+		/// membership has already expired, the period counts from now.
+		///
+		/// Only the holder of the membership or the community account can
+		/// renew it.
+		#[pallet::call_index(6)]
+		pub fn renew_membership(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let community_account = pallet_communities::Pallet::<T>::community_account(&community_id);
+			ensure!(
+				who == community_account
+					|| T::CreateMemberships::owner(&community_id, &membership_id).as_ref() == Some(&who),
+				Error::<T>::NotMembershipHolder
+			);
+
+			let RenewalTerms {
+				price: (asset, price),
+				period,
+			} = MembershipRenewalTerms::<T>::get(community_id).ok_or(Error::<T>::NoRenewalTerms)?;
+
+			let m: MembershipIdOf<T> = membership_id.clone().into();
+			let expiration = T::MembershipExpiry::expiration_of(&community_id, &m)
+				.ok_or(Error::<T>::MembershipDoesNotExpire)?
+				.max(frame_system::Pallet::<T>::block_number())
+				.saturating_add(period);
+
+			Self::do_pay_revenue(&who, &community_id, MembershipPrice::Asset(asset, price))?;
+
+			T::CreateMemberships::set_typed_attribute(
+				&community_id,
+				&membership_id,
+				&b"membership_expiration",
+				&expiration,
+			)?;
+			pallet_communities::Pallet::<T>::track_membership_expiry(&community_id, &m);
+
+			Self::deposit_event(Event::<T>::MembershipRenewed {
+				community_id,
+				membership_id,
+				expiration,
+			});
+			Ok(())
+		}
+
+		/// Schedules the creation of a large amount of memberships, which are
+		/// minted in chunks either when blocks have weight to spare or via
+		/// [`continue_minting`](Pallet::continue_minting).
+		#[pallet::call_index(7)]
+		pub fn schedule_minting(
+			origin: OriginFor<T>,
+			amount: u32,
+			starting_at: <T as Config>::MembershipId,
+			#[pallet::compact] price: NativeBalanceOf<T>,
+			tank_config: TankConfig<Weight, BlockNumberFor<T>>,
+			maybe_expiration: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure!(
+				amount <= MAX_MINTING_JOB_MEMBERSHIPS,
+				Error::<T>::CreatingTooManyMemberships
+			);
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+
+			let job_id = NextMintingJobId::<T>::mutate(|id| {
+				let job_id = *id;
+				id.saturating_inc();
+				job_id
+			});
+			MintingJobs::<T>::insert(
+				job_id,
+				MintingJob {
+					next_id: starting_at.clone(),
+					remaining: amount,
+					minted: 0,
+					price,
+					tank_config,
+					maybe_expiration,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::MintingJobScheduled {
+				job_id,
+				starting_at,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Creates up to `max` memberships of a pending minting job.
+		#[pallet::weight(<T as Config>::WeightInfo::create_memberships((*max).into()))]
+		#[pallet::call_index(8)]
+		pub fn continue_minting(origin: OriginFor<T>, job_id: u32, max: u16) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let max = (max as u32).min(MAX_MINTING_CHUNK);
+
+			let processed = Self::do_continue_minting(job_id, max)?;
+
+			Ok(Some(<T as Config>::WeightInfo::create_memberships(processed)).into())
+		}
+
+		/// Drops a minting job. Memberships that have already been created
+		/// are kept.
+		#[pallet::call_index(9)]
+		pub fn cancel_minting(origin: OriginFor<T>, job_id: u32) -> DispatchResult {
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+			let job = MintingJobs::<T>::take(job_id).ok_or(Error::<T>::MintingJobNotFound)?;
+
+			Self::deposit_event(Event::<T>::MintingJobCancelled {
+				job_id,
+				minted: job.minted,
+			});
+			Ok(())
+		}
+
+		/// Burns the unsold memberships within the given range, clearing
+		/// their gas tanks. Memberships that don't exist or have already been
+		/// sold are skipped.
+		#[pallet::weight(<T as Config>::WeightInfo::recall_memberships((*amount).into()))]
+		#[pallet::call_index(11)]
+		pub fn recall_memberships(
+			origin: OriginFor<T>,
+			starting_at: <T as Config>::MembershipId,
+			amount: u16,
+		) -> DispatchResult {
+			ensure!(amount <= 1024u16, Error::<T>::TooManyMemberships);
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+			let recalled =
+				Self::for_each_unsold(&starting_at, amount, |id| Self::do_recall_membership(collection_id, id))?;
+
+			Self::deposit_event(Event::<T>::MembershipsRecalled {
+				starting_at,
+				amount: amount.into(),
+				recalled,
+			});
+			Ok(())
+		}
+
+		/// Changes the price of the unsold memberships within the given
+		/// range. Memberships that don't exist or have already been sold are
+		/// skipped.
+		#[pallet::weight(<T as Config>::WeightInfo::reprice_memberships((*amount).into()))]
+		#[pallet::call_index(12)]
+		pub fn reprice_memberships(
+			origin: OriginFor<T>,
+			starting_at: <T as Config>::MembershipId,
+			amount: u16,
+			price: MembershipPriceOf<T>,
+		) -> DispatchResult {
+			ensure!(amount <= 1024u16, Error::<T>::TooManyMemberships);
+			T::CreateMembershipsOrigin::ensure_origin(origin)?;
+
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+			let repriced = Self::for_each_unsold(&starting_at, amount, |id| {
+				Self::do_reprice_membership(collection_id, id, &price)
+			})?;
+
+			Self::deposit_event(Event::<T>::MembershipsRepriced {
+				starting_at,
+				amount: amount.into(),
+				repriced,
+				price,
+			});
+			Ok(())
+		}
+
+		/// Fills the gas tank of a member of the community with the given
+		/// capacity. The community account must be able to afford the gas,
+		/// which is charged to it as it gets consumed.
+		#[pallet::call_index(13)]
+		pub fn fill_member_gas_tank(
+			origin: OriginFor<T>,
+			membership_id: <T as Config>::MembershipId,
+			capacity: Weight,
+		) -> DispatchResult {
+			let community_id = <T as pallet_communities::Config>::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CreateMemberships::owner(&community_id, &membership_id).is_some(),
+				Error::<T>::UnknownMembership
+			);
+
+			Self::do_fill_member_gas_tank(&community_id, &membership_id, capacity)
+		}
+
+		/// Schedules periodic refills of the gas tank of a member of the
+		/// community, or stops them when `None` is given. The first refill can
+		/// be claimed right away.
+		#[pallet::call_index(14)]
+		pub fn set_member_gas_refills(
+			origin: OriginFor<T>,
+			membership_id: <T as Config>::MembershipId,
+			maybe_refill: Option<(Weight, BlockNumberFor<T>)>,
+		) -> DispatchResult {
+			let community_id = <T as pallet_communities::Config>::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CreateMemberships::owner(&community_id, &membership_id).is_some(),
+				Error::<T>::UnknownMembership
+			);
+
+			let refill = maybe_refill.map(|(capacity, period)| GasRefill {
+				capacity,
+				period,
+				next_at: frame_system::Pallet::<T>::block_number(),
+			});
+			MemberGasRefills::<T>::set(&membership_id, community_id, refill.clone());
+
+			Self::deposit_event(Event::<T>::MemberGasRefillsSet {
+				community_id,
+				membership_id,
+				refill,
+			});
+			Ok(())
+		}
+
+		/// Claims the next scheduled refill of the gas tank of a member, which
+		/// is charged to the community account.
+		#[pallet::call_index(15)]
+		pub fn refill_member_gas_tank(
+			origin: OriginFor<T>,
+			community_id: CommunityIdOf<T>,
+			membership_id: <T as Config>::MembershipId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let mut refill =
+				MemberGasRefills::<T>::get(&membership_id, community_id).ok_or(Error::<T>::NoGasRefills)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(refill.next_at <= now, Error::<T>::GasRefillNotDue);
+
+			Self::do_fill_member_gas_tank(&community_id, &membership_id, refill.capacity)?;
+
+			refill.next_at = now.saturating_add(refill.period);
+			MemberGasRefills::<T>::insert(membership_id, community_id, refill);
+			Ok(())
+		}
+
+		/// Sets (or removes) a track template communities can be registered
+		/// with.
+		#[pallet::call_index(10)]
+		pub fn set_track_template(
+			origin: OriginFor<T>,
+			id: TrackTemplateId,
+			maybe_track_info: Option<TrackInfoOf<T>>,
+		) -> DispatchResult {
+			T::TrackTemplatesOrigin::ensure_origin(origin)?;
+
+			match maybe_track_info {
+				Some(track_info) => {
+					Self::validate_track_info(&track_info)?;
+					TrackTemplates::<T>::insert(id, track_info);
+					Self::deposit_event(Event::<T>::TrackTemplateSet { id });
+				}
+				None => {
+					TrackTemplates::<T>::take(id).ok_or(Error::<T>::TrackTemplateNotFound)?;
+					Self::deposit_event(Event::<T>::TrackTemplateRemoved { id });
+				}
+			}
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Mints a membership into the manager collection and puts it for sale
+		/// at the given price.
+		fn do_create_membership_for_sale(
+			collection_id: &CommunityIdOf<T>,
+			id: &<T as Config>::MembershipId,
+			price: NativeBalanceOf<T>,
+			tank_config: &TankConfig<Weight, BlockNumberFor<T>>,
+			maybe_expiration: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			Self::do_create_membership(collection_id, id, tank_config, maybe_expiration)?;
+			T::CreateMemberships::set_price(collection_id, id, &T::MembershipsManagerOwner::get(), Some(price), None)
+		}
+
+		/// Replaces the gas tank of the membership with a tank of the given
+		/// capacity (keeping the periodicity it was created with), as long as
+		/// the community account can afford that much gas.
+		pub(crate) fn do_fill_member_gas_tank(
+			community_id: &CommunityIdOf<T>,
+			membership_id: &<T as Config>::MembershipId,
+			capacity: Weight,
+		) -> DispatchResult {
+			use frame_support::traits::{fungible::Inspect, tokens::Fortitude};
+			let community_account = pallet_communities::Pallet::<T>::community_account(community_id);
+			let available = <T as pallet_communities::Config>::Balances::reducible_balance(
+				&community_account,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			);
+			ensure!(
+				available >= T::GasToFee::weight_to_fee(&capacity),
+				Error::<T>::InsufficientFundsForGas
+			);
+			T::MakeTank::make_tank(
+				&(*community_id, membership_id.clone()),
+				Some(capacity),
+				GasTankPeriodicity::<T>::get(membership_id),
+			)?;
+			GasTankPayers::<T>::insert(membership_id, community_id, community_account);
+
+			Self::deposit_event(Event::<T>::MemberGasTankFilled {
+				community_id: *community_id,
+				membership_id: membership_id.clone(),
+				capacity,
+			});
+			Ok(())
+		}
+
+		/// Runs `f` on every membership of the given range that is still
+		/// held by the manager, returning how many of them were affected.
+		fn for_each_unsold(
+			starting_at: &<T as Config>::MembershipId,
+			amount: u16,
+			mut f: impl FnMut(&<T as Config>::MembershipId) -> DispatchResult,
+		) -> Result<u32, DispatchError> {
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+			let manager = T::MembershipsManagerOwner::get();
+
+			let mut id = starting_at.clone();
+			let mut affected = 0u32;
+			for _ in 0..amount {
+				if T::CreateMemberships::owner(collection_id, &id).as_ref() == Some(&manager) {
+					f(&id)?;
+					affected.saturating_inc();
+				}
+				let Some(next_id) = id.increment() else {
+					break;
+				};
+				id = next_id;
+			}
+
+			Ok(affected)
+		}
+
+		/// Burns a membership of the manager collection, clearing the
+		/// attributes (gas tank, expiration and rank) it was given when
+		/// created, along with any gas refills left from a previous holder.
+		pub(crate) fn do_recall_membership(
+			collection_id: &CommunityIdOf<T>,
+			id: &<T as Config>::MembershipId,
+		) -> DispatchResult {
+			for key in [
+				&b"membership_gas"[..],
+				&b"membership_expiration"[..],
+				&b"membership_member_rank"[..],
+			] {
+				if T::CreateMemberships::system_attribute(collection_id, Some(id), key).is_some() {
+					T::CreateMemberships::clear_attribute(collection_id, id, key)?;
+				}
+			}
+			MembershipPrices::<T>::remove(id);
+			GasTankPeriodicity::<T>::remove(id);
+			let _ = MemberGasRefills::<T>::clear_prefix(id, u32::MAX, None);
+			let _ = GasTankPayers::<T>::clear_prefix(id, u32::MAX, None);
+			T::CreateMemberships::burn(collection_id, id, None)
+		}
+
+		pub(crate) fn do_reprice_membership(
+			collection_id: &CommunityIdOf<T>,
+			id: &<T as Config>::MembershipId,
+			price: &MembershipPriceOf<T>,
+		) -> DispatchResult {
+			let manager = T::MembershipsManagerOwner::get();
+			match price {
+				MembershipPrice::Native(price) => {
+					MembershipPrices::<T>::remove(id);
+					T::CreateMemberships::set_price(collection_id, id, &manager, Some(*price), None)
+				}
+				MembershipPrice::Asset(asset, price) => {
+					MembershipPrices::<T>::insert(id, (asset.clone(), *price));
+					T::CreateMemberships::set_price(collection_id, id, &manager, None, None)
+				}
+			}
+		}
+
+		/// Creates up to `max` memberships of a minting job. Memberships that
+		/// already exist are skipped (but counted as processed), so running
+		/// the same chunk twice can neither mint twice nor skip an ID.
+		pub(crate) fn do_continue_minting(job_id: u32, max: u32) -> Result<u32, DispatchError> {
+			let mut job = MintingJobs::<T>::get(job_id).ok_or(Error::<T>::MintingJobNotFound)?;
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+
+			let mut processed = 0u32;
+			while processed < max && job.remaining > 0 {
+				if T::CreateMemberships::owner(collection_id, &job.next_id).is_none() {
+					Self::do_create_membership_for_sale(
+						collection_id,
+						&job.next_id,
+						job.price,
+						&job.tank_config,
+						job.maybe_expiration,
+					)?;
+					job.minted.saturating_inc();
+				}
+				processed.saturating_inc();
+				job.remaining.saturating_dec();
+
+				match job.next_id.increment() {
+					Some(next_id) => job.next_id = next_id,
+					// There are no more IDs to mint
+					None => job.remaining = 0,
+				}
+			}
+
+			if job.remaining == 0 {
+				MintingJobs::<T>::remove(job_id);
+				Self::deposit_event(Event::<T>::MintingJobFinished {
+					job_id,
+					minted: job.minted,
+				});
+			} else {
+				Self::deposit_event(Event::<T>::MintingJobProgressed {
+					job_id,
+					minted: job.minted,
+					remaining: job.remaining,
+				});
+				MintingJobs::<T>::insert(job_id, job);
+			}
+
+			Ok(processed)
+		}
+
+		/// Advances the first pending minting job (in the order they were
+		/// scheduled) as much as the remaining weight of the block allows.
+		pub(crate) fn mint_on_idle(remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Reading and moving the cursor, along with the ID of the next job
+			let mut used = db_weight.reads_writes(2, 1);
+			if used.any_gt(remaining_weight) {
+				return Weight::zero();
+			}
+
+			// Jobs finished or cancelled are skipped
+			let next_job_id = NextMintingJobId::<T>::get();
+			let mut job_id = FirstPendingMintingJob::<T>::get();
+			let found = loop {
+				if job_id >= next_job_id || used.saturating_add(db_weight.reads(1)).any_gt(remaining_weight) {
+					break false;
+				}
+				used.saturating_accrue(db_weight.reads(1));
+				if MintingJobs::<T>::contains_key(job_id) {
+					break true;
+				}
+				job_id.saturating_inc();
+			};
+			FirstPendingMintingJob::<T>::put(job_id);
+			if !found {
+				return used;
+			}
+
+			let base = <T as Config>::WeightInfo::create_memberships(0);
+			let per_membership = <T as Config>::WeightInfo::create_memberships(1).saturating_sub(base);
+			let chunk = remaining_weight
+				.saturating_sub(used)
+				.saturating_sub(base)
+				.checked_div_per_component(&per_membership)
+				.unwrap_or_default()
+				.min(MAX_MINTING_CHUNK as u64) as u32;
+			if chunk == 0 {
+				return used;
+			}
+
+			let result = frame_support::storage::with_storage_layer(|| Self::do_continue_minting(job_id, chunk));
+			match result {
+				Ok(processed) => used.saturating_add(<T as Config>::WeightInfo::create_memberships(processed)),
+				Err(error) => {
+					// The job is left for `continue_minting` to resume, without holding back
+					// the ones scheduled after it
+					FirstPendingMintingJob::<T>::put(job_id.saturating_add(1));
+					let remaining = MintingJobs::<T>::get(job_id).map_or(0, |job| job.remaining);
+					Self::deposit_event(Event::<T>::MintingJobFailed {
+						job_id,
+						remaining,
+						error,
+					});
+					// The chunk might have failed at any point, so it's charged in full
+					used.saturating_add(<T as Config>::WeightInfo::create_memberships(chunk))
+						.saturating_add(db_weight.reads(1))
+				}
+			}
+		}
+
+		/// Mints a membership into the manager collection, setting up its gas
+		/// tank and (optionally) its expiration.
+		pub(crate) fn do_create_membership(
+			collection_id: &CommunityIdOf<T>,
+			id: &<T as Config>::MembershipId,
+			tank_config: &TankConfig<Weight, BlockNumberFor<T>>,
+			maybe_expiration: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			T::CreateMemberships::mint_into(
+				collection_id,
+				id,
+				&T::MembershipsManagerOwner::get(),
+				&Default::default(),
+				true,
+			)?;
+
+			Self::do_set_gas_tank(&(*collection_id, id.clone()), tank_config)?;
+
+			if let Some(expiration) = maybe_expiration {
+				T::CreateMemberships::set_typed_attribute(collection_id, id, &b"membership_expiration", &expiration)?;
+			}
+
+			Ok(())
+		}
+
+		/// Takes a membership from the manager collection (minting it if
+		/// allowed) and assigns it to a founding member of the community.
+		pub(crate) fn do_add_founder(
+			community_id: &CommunityIdOf<T>,
+			who: &AccountIdOf<T>,
+			membership_id: &<T as Config>::MembershipId,
+			rank: GenericRank,
+			maybe_payer: Option<&AccountIdOf<T>>,
+		) -> DispatchResult {
+			let collection_id = &T::MembershipsManagerCollectionId::get();
+			let manager = T::MembershipsManagerOwner::get();
+
+			match T::CreateMemberships::owner(collection_id, membership_id) {
+				None => {
+					ensure!(maybe_payer.is_none(), Error::<T>::MembershipNotAvailable);
+					Self::do_create_membership(collection_id, membership_id, &Default::default(), None)?;
+				}
+				Some(owner) => {
+					ensure!(owner == manager, Error::<T>::MembershipNotAvailable);
+					let maybe_price = match MembershipPrices::<T>::take(membership_id) {
+						Some((asset, price)) => Some(MembershipPrice::Asset(asset, price)),
+						None => {
+							T::CreateMemberships::item_price(collection_id, membership_id).map(MembershipPrice::Native)
+						}
+					};
+					// Registering for free (i.e. approved by governance) takes the
+					// memberships without paying their price
+					if let (Some(payer), Some(price)) = (maybe_payer, maybe_price) {
+						Self::do_pay_revenue(payer, community_id, price)?;
+					}
+				}
+			}
+
+			T::CreateMemberships::transfer(
+				collection_id,
+				membership_id,
+				&pallet_communities::Pallet::<T>::community_account(community_id),
+			)?;
+			let m: MembershipIdOf<T> = membership_id.clone().into();
+			T::MemberMgmt::assign(community_id, &m, who)?;
+			T::MemberMgmt::set_rank(community_id, &m, rank)?;
+			pallet_communities::Pallet::<T>::track_membership_expiry(community_id, &m);
+
+			Self::deposit_event(Event::<T>::FoundingMemberAdded {
+				community_id: *community_id,
+				who: who.clone(),
+				membership_id: membership_id.clone(),
+				rank,
+			});
+			Ok(())
+		}
+
+		/// Pays the price of a membership, splitting the revenue between the
+		/// community and the owner of the manager collection.
+		fn do_pay_revenue(
+			who: &AccountIdOf<T>,
+			community_id: &CommunityIdOf<T>,
+			price: MembershipPriceOf<T>,
+		) -> DispatchResult {
+			let community_account = pallet_communities::Pallet::<T>::community_account(community_id);
+			let manager = T::MembershipsManagerOwner::get();
+			match price {
+				MembershipPrice::Native(price) => {
+					let community_share = T::CommunityRevenueShare::get() * price;
+					for (to, amount) in [
+						(&community_account, community_share),
+						(&manager, price.saturating_sub(community_share)),
+					] {
+						if !amount.is_zero() {
+							<T as pallet_communities::Config>::Balances::transfer(
+								who,
+								to,
+								amount,
+								Preservation::Preserve,
+							)?;
+						}
+					}
+					Ok(())
+				}
+				MembershipPrice::Asset(asset, price) => {
+					let community_share = T::CommunityRevenueShare::get() * price;
+					Self::do_pay(&asset, who, &community_account, community_share)?;
+					Self::do_pay(&asset, who, &manager, price.saturating_sub(community_share))
+				}
+			}
+		}
+
+		fn do_pay(
+			asset: &AssetIdOf<T>,
+			from: &AccountIdOf<T>,
+			to: &AccountIdOf<T>,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(());
+			}
+			T::Assets::transfer(asset.clone(), from, to, amount, Preservation::Preserve)?;
+			Ok(())
+		}
+
+		pub(crate) fn tiers_total(tiers: &MembershipTiersOf<T>) -> u32 {
+			tiers
+				.iter()
+				.fold(0u32, |total, tier| total.saturating_add(tier.amount.into()))
+		}
+
 		#[inline]
 		pub(crate) fn do_set_gas_tank(
 			tank_id: &(CommunityIdOf<T>, <T as Config>::MembershipId),
@@ -279,31 +1349,62 @@ pub mod pallet {
 		) -> DispatchResult {
 			let TankConfig { capacity, periodicity } = config;
 			T::MakeTank::make_tank(tank_id, *capacity, *periodicity)?;
+			GasTankPeriodicity::<T>::set(&tank_id.1, *periodicity);
+			GasTankPayers::<T>::remove(&tank_id.1, tank_id.0);
 
 			Ok(())
 		}
 
-		fn default_tack(name: &str) -> TrackInfoOf<T> {
-			use sp_runtime::Perbill;
-			TrackInfo {
+		/// Resolves the track a community is registered with, either from a
+		/// template or from custom (validated) parameters.
+		pub(crate) fn community_track_info(
+			name: &str,
+			maybe_track: Option<CommunityTrackOf<T>>,
+		) -> Result<TrackInfoOf<T>, DispatchError> {
+			let track_info = match maybe_track {
+				Some(CommunityTrack::Template(id)) => {
+					TrackTemplates::<T>::get(id).ok_or(Error::<T>::TrackTemplateNotFound)?
+				}
+				Some(CommunityTrack::Custom(track_info)) => {
+					Self::validate_track_info(&track_info)?;
+					return Ok(track_info);
+				}
+				None => TrackTemplates::<T>::get(DEFAULT_TRACK_TEMPLATE).ok_or(Error::<T>::TrackTemplateNotFound)?,
+			};
+
+			Ok(TrackInfo {
 				name: str_array(name),
-				max_deciding: 1,
-				decision_deposit: 0u8.into(),
-				prepare_period: 1u8.into(),
-				decision_period: u8::MAX.into(),
-				confirm_period: 1u8.into(),
-				min_enactment_period: 1u8.into(),
-				min_approval: pallet_referenda::Curve::LinearDecreasing {
-					length: Perbill::from_percent(100),
-					floor: Perbill::from_percent(50),
-					ceil: Perbill::from_percent(100),
-				},
-				min_support: pallet_referenda::Curve::LinearDecreasing {
-					length: Perbill::from_percent(100),
-					floor: Perbill::from_percent(0),
-					ceil: Perbill::from_percent(50),
-				},
-			}
+				..track_info
+			})
+		}
+
+		/// Checks that referenda on a track can actually be decided: at
+		/// least one referendum can be deciding at a time, the confirmation
+		/// fits in the decision period, and approval can't end up below a
+		/// simple majority.
+		pub(crate) fn validate_track_info(track_info: &TrackInfoOf<T>) -> DispatchResult {
+			use sp_runtime::Perbill;
+			ensure!(track_info.max_deciding > 0, Error::<T>::InvalidTrackInfo);
+			ensure!(!track_info.decision_period.is_zero(), Error::<T>::InvalidTrackInfo);
+			ensure!(!track_info.confirm_period.is_zero(), Error::<T>::InvalidTrackInfo);
+			ensure!(
+				track_info.confirm_period <= track_info.decision_period,
+				Error::<T>::InvalidTrackInfo
+			);
+			ensure!(
+				track_info.min_approval.threshold(Perbill::one()) >= Perbill::from_percent(50),
+				Error::<T>::InvalidTrackInfo
+			);
+			Ok(())
 		}
 	}
 }
+
+/// A membership released back to its community no longer gets the gas
+/// refills the community scheduled for its former holder.
+impl<T: Config> pallet_communities::OnMembershipReleased<CommunityIdOf<T>, <T as Config>::MembershipId> for Pallet<T> {
+	fn on_membership_released(community_id: &CommunityIdOf<T>, membership_id: &<T as Config>::MembershipId) {
+		MemberGasRefills::<T>::remove(membership_id, community_id);
+		GasTankPayers::<T>::remove(membership_id, community_id);
+	}
+}
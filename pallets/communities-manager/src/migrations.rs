@@ -0,0 +1,36 @@
+//! Storage migrations for the communities manager pallet.
+
+use super::*;
+
+pub mod v1 {
+	use super::*;
+	use core::marker::PhantomData;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// Sets the [initial track templates](Config::InitialTrackTemplates) on
+	/// chains that were running before the templates were seeded at genesis.
+	/// Templates that have already been set are kept as they are.
+	pub struct SeedTrackTemplatesInner<T>(PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for SeedTrackTemplatesInner<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let (mut reads, mut writes) = (0u64, 0u64);
+			for (id, track_info) in T::InitialTrackTemplates::get() {
+				reads.saturating_inc();
+				if TrackTemplates::<T>::contains_key(id) {
+					continue;
+				}
+				if Pallet::<T>::validate_track_info(&track_info).is_err() {
+					log::warn!(target: "runtime::communities-manager", "skipping invalid track template {id}");
+					continue;
+				}
+				TrackTemplates::<T>::insert(id, track_info);
+				writes.saturating_inc();
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+
+	/// Seeds the track templates, moving the pallet to version 1.
+	pub type SeedTrackTemplates<T> =
+		VersionedMigration<0, 1, SeedTrackTemplatesInner<T>, Pallet<T>, <T as frame_system::Config>::DbWeight>;
+}
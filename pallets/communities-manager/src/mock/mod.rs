@@ -3,15 +3,22 @@ use fc_traits_gas_tank::NonFungibleGasTank;
 use fc_traits_memberships::NonFungiblesMemberships;
 use frame_support::{
 	assert_ok, derive_impl, parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, EitherOf, EqualPrivilegeOnly, VariantCountOf},
+	traits::{
+		AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, EitherOf, EnsureOrigin, EqualPrivilegeOnly, MapSuccess,
+		OriginTrait, VariantCountOf,
+	},
+	weights::IdentityFee,
 	PalletId,
 };
 use frame_system::{EnsureNever, EnsureRoot, EnsureRootWithSuccess, EnsureSigned};
-use pallet_communities::{origin::EnsureCommunity, Tally, VoteWeight};
+use pallet_communities::{
+	origin::{EnsureCommunity, EnsureCommunityCollective},
+	NonFungiblesMembershipExpiry, Tally, VoteWeight,
+};
 use sp_io::TestExternalities;
 use sp_runtime::{
 	traits::{IdentifyAccount, IdentityLookup, Verify},
-	MultiSignature,
+	MultiSignature, Perbill, Percent,
 };
 pub use virto_common::{CommunityId, MembershipId};
 
@@ -169,6 +176,29 @@ parameter_types! {
 	pub const MembershipNftAttr: &'static [u8; 10] = b"membership";
 	pub const TestCommunity: CommunityId = 1;
 	pub const NoDepositOnRootRegistration: Deposit = None;
+	pub const RemoteAccount: AccountId = AccountId::new([0xaa; 32]);
+	pub const CommunityRevenueShare: Percent = Percent::from_percent(80);
+}
+
+/// Stands in for a remote location (like a sibling chain registering via
+/// XCM): it pays the deposit and becomes the admin of the community.
+pub struct EnsureRemoteAdmin;
+impl EnsureOrigin<RuntimeOrigin> for EnsureRemoteAdmin {
+	type Success = (Deposit, Option<OriginCaller>);
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(frame_system::RawOrigin::Signed(who)) if who == RemoteAccount::get() => {
+				Ok((Some((10, who, RootAccount::get())), Some(o.into_caller())))
+			}
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(RemoteAccount::get()))
+	}
 }
 
 impl pallet_nfts::Config for Test {
@@ -194,7 +224,7 @@ impl pallet_nfts::Config for Test {
 	type OffchainPublic = AccountPublic;
 	type OffchainSignature = MultiSignature;
 	type RuntimeEvent = RuntimeEvent;
-	type StringLimit = ();
+	type StringLimit = ConstU32<256>;
 	type ValueLimit = ConstU32<40>;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
@@ -206,10 +236,17 @@ impl pallet_communities::Config for Test {
 	type CommunityId = CommunityId;
 	type MembershipId = MembershipId;
 	type Assets = Assets;
+	type AssetNamespace = ();
 	type AssetsFreezer = AssetsFreezer;
 	type Balances = Balances;
 	type ItemConfig = pallet_nfts::ItemConfig;
 	type MemberMgmt = NonFungiblesMemberships<Memberships>;
+	type MembershipExpiry = NonFungiblesMembershipExpiry<Memberships, AccountId>;
+	type ExpiredMembershipGracePeriod = ConstU64<10>;
+	type OnMembershipReleased = CommunitiesManager;
+	type RecoveryOrigin = EnsureRoot<AccountId>;
+	type AdminRecoveryVetoOrigin = EnsureCommunityCollective<Self>;
+	type MinAdminRecoveryPeriod = ConstU64<10>;
 	type Polls = Referenda;
 	type CreateOrigin = EnsureNever<Deposit>;
 	type AdminOrigin = EnsureCommunity<Self>;
@@ -223,21 +260,56 @@ impl pallet_communities::Config for Test {
 	type BenchmarkHelper = CommunityBenchmarkHelper;
 }
 
+parameter_types! {
+	pub InitialTrackTemplates: Vec<(TrackTemplateId, TrackInfoOf<Test>)> = vec![(
+		DEFAULT_TRACK_TEMPLATE,
+		pallet_referenda::TrackInfo {
+			name: sp_runtime::str_array("default"),
+			max_deciding: 1,
+			decision_deposit: 1,
+			prepare_period: 1,
+			decision_period: 10,
+			confirm_period: 1,
+			min_enactment_period: 1,
+			min_approval: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(50),
+				ceil: Perbill::from_percent(100),
+			},
+			min_support: pallet_referenda::Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(0),
+				ceil: Perbill::from_percent(50),
+			},
+		},
+	)];
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	// Types to support community creation
 	type CreateCollection = Memberships;
 	type Tracks = Tracks;
 	type RankedCollective = Collective;
-	type RegisterOrigin = EnsureRootWithSuccess<AccountId, NoDepositOnRootRegistration>;
+	type RegisterOrigin = EitherOf<
+		MapSuccess<
+			EnsureRootWithSuccess<AccountId, NoDepositOnRootRegistration>,
+			WithoutAdminOrigin<Deposit, OriginCaller>,
+		>,
+		EnsureRemoteAdmin,
+	>;
 	// Types to support memberships creation
 	type CreateMembershipsOrigin = EnsureRoot<AccountId>;
+	type TrackTemplatesOrigin = EnsureRoot<AccountId>;
+	type InitialTrackTemplates = InitialTrackTemplates;
 	type MembershipId = MembershipId;
 
 	type MembershipsManagerCollectionId = MembershipsManagerCollectionId;
 	type MembershipsManagerOwner = RootAccount;
 	type CreateMemberships = Memberships;
 	type MakeTank = NonFungibleGasTank<Test, Memberships, pallet_nfts::ItemConfig>;
+	type CommunityRevenueShare = CommunityRevenueShare;
+	type GasToFee = IdentityFee<Balance>;
 
 	type WeightInfo = WeightInfo;
 }
@@ -246,6 +318,7 @@ pub fn new_test_ext() -> TestExternalities {
 	let mut t = TestExternalities::new(Default::default());
 
 	t.execute_with(|| {
+		crate::GenesisConfig::<Test>::default().build();
 		assert_ok!(Memberships::create_collection_with_id(
 			MembershipsManagerCollectionId::get(),
 			&RootAccount::get(),
@@ -2,10 +2,488 @@ use crate::mock::new_test_ext;
 use crate::{
 	mock::*,
 	weights::{SubstrateWeight, WeightInfo},
-	TankConfig,
+	CommunityTrack, Error, FirstPendingMintingJob, GasRefill, GasTankPayers, GasTankPeriodicity, MemberGasRefills,
+	MembershipPrice, MembershipPrices, MembershipRenewalTerms, MembershipTier, MintingJobs, RenewalTerms, TankConfig,
+	TrackTemplates, MAX_FOUNDERS, MAX_TIERED_MEMBERSHIPS,
+};
+use fc_traits_memberships::{GenericRank, Inspect as MembershipsInspect, Rank};
+use frame_support::traits::{
+	fungibles::{Inspect as AssetsInspect, Mutate as AssetsMutate},
+	nonfungibles_v2::{Inspect, Mutate, Transfer},
 };
-use frame_support::assert_ok;
 use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok, traits::Hooks, BoundedVec};
+use pallet_referenda::{Curve, TrackInfo};
+use parity_scale_codec::Encode;
+use sp_runtime::{str_array, DispatchError, DispatchResult, Perbill};
+
+mod recall_and_reprice {
+	use super::*;
+
+	fn collection() -> CommunityId {
+		MembershipsManagerCollectionId::get()
+	}
+
+	fn setup() {
+		System::set_block_number(1);
+		assert_ok!(CommunitiesManager::create_memberships(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			10,
+			TankConfig {
+				capacity: Some(Weight::MAX),
+				periodicity: None,
+			},
+			Some(100),
+		));
+		// Membership 2 has been sold
+		assert_ok!(<Memberships as Transfer<AccountId>>::transfer(
+			&collection(),
+			&2,
+			&AccountId::new([5; 32]),
+		));
+	}
+
+	#[test]
+	fn recall_memberships_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+			// Refills left from a former holder of the membership
+			MemberGasRefills::<Test>::insert(
+				1,
+				COMMUNITY,
+				GasRefill {
+					capacity: Weight::MAX,
+					period: 10,
+					next_at: 1,
+				},
+			);
+
+			assert_noop!(
+				CommunitiesManager::recall_memberships(RuntimeOrigin::signed(AccountId::new([1; 32])), 1, 3),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(CommunitiesManager::recall_memberships(RuntimeOrigin::root(), 1, 3));
+			assert_eq!(MemberGasRefills::<Test>::get(1, COMMUNITY), None);
+
+			assert_eq!(Memberships::owner(&collection(), &1), None);
+			assert_eq!(Memberships::owner(&collection(), &2), Some(AccountId::new([5; 32])));
+			assert_eq!(Memberships::owner(&collection(), &3), None);
+			assert_eq!(Memberships::owner(&collection(), &4), Some(RootAccount::get()));
+			assert_eq!(
+				Memberships::system_attribute(&collection(), Some(&1), b"membership_gas"),
+				None
+			);
+			assert_eq!(
+				Memberships::system_attribute(&collection(), Some(&1), b"membership_expiration"),
+				None
+			);
+			System::assert_has_event(
+				crate::Event::<Test>::MembershipsRecalled {
+					starting_at: 1,
+					amount: 3,
+					recalled: 2,
+				}
+				.into(),
+			);
+
+			// Recalled memberships can be created again
+			assert_ok!(CommunitiesManager::create_memberships(
+				RuntimeOrigin::root(),
+				1,
+				1,
+				10,
+				TankConfig::default(),
+				None,
+			));
+		})
+	}
+
+	#[test]
+	fn recall_and_reprice_fail_with_too_many_memberships() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			assert_noop!(
+				CommunitiesManager::recall_memberships(RuntimeOrigin::root(), 1, 1025),
+				Error::<Test>::TooManyMemberships
+			);
+			assert_noop!(
+				CommunitiesManager::reprice_memberships(RuntimeOrigin::root(), 1, 1025, MembershipPrice::Native(20)),
+				Error::<Test>::TooManyMemberships
+			);
+		})
+	}
+
+	#[test]
+	fn reprice_memberships_works() {
+		new_test_ext().execute_with(|| {
+			setup();
+
+			assert_ok!(CommunitiesManager::reprice_memberships(
+				RuntimeOrigin::root(),
+				1,
+				2,
+				MembershipPrice::Asset(ASSET, 50)
+			));
+			assert_eq!(MembershipPrices::<Test>::get(1), Some((ASSET, 50)));
+			assert_eq!(MembershipPrices::<Test>::get(2), None);
+			assert_eq!(pallet_nfts::ItemPriceOf::<Test>::get(collection(), 1), None);
+			System::assert_has_event(
+				crate::Event::<Test>::MembershipsRepriced {
+					starting_at: 1,
+					amount: 2,
+					repriced: 1,
+					price: MembershipPrice::Asset(ASSET, 50),
+				}
+				.into(),
+			);
+
+			assert_ok!(CommunitiesManager::reprice_memberships(
+				RuntimeOrigin::root(),
+				1,
+				5,
+				MembershipPrice::Native(20)
+			));
+			assert_eq!(MembershipPrices::<Test>::get(1), None);
+			assert_eq!(
+				pallet_nfts::ItemPriceOf::<Test>::get(collection(), 1).map(|(price, _)| price),
+				Some(20)
+			);
+			assert_eq!(
+				pallet_nfts::ItemPriceOf::<Test>::get(collection(), 5).map(|(price, _)| price),
+				Some(20)
+			);
+		})
+	}
+}
+
+#[test]
+fn register_with_admin_origin_from_register_origin_works() {
+	new_test_ext().execute_with(|| {
+		let remote = RemoteAccount::get();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), remote.clone(), 100));
+
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::signed(remote.clone()),
+			COMMUNITY,
+			BoundedVec::truncate_from(b"Remote Community".to_vec()),
+			AccountId::new([2; 32]),
+			None,
+			None,
+			Default::default(),
+		));
+		// The deposit was paid by the remote origin
+		assert_eq!(Balances::free_balance(&remote), 90);
+
+		// The remote origin is the admin, not `first_admin`
+		assert_noop!(
+			CommunitiesManager::set_renewal_terms(RuntimeOrigin::signed(AccountId::new([2; 32])), None),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(CommunitiesManager::set_renewal_terms(
+			RuntimeOrigin::signed(remote),
+			None
+		));
+	})
+}
+
+mod founders {
+	use super::*;
+
+	fn founders(list: Vec<(u8, MembershipId, u8)>) -> crate::FoundersOf<Test> {
+		BoundedVec::truncate_from(
+			list.into_iter()
+				.map(|(who, id, rank)| (AccountId::new([who; 32]), id, GenericRank::from(rank)))
+				.collect(),
+		)
+	}
+
+	fn register(origin: RuntimeOrigin, founders: crate::FoundersOf<Test>) -> DispatchResult {
+		CommunitiesManager::register(
+			origin,
+			COMMUNITY,
+			BoundedVec::truncate_from(b"Test Community".to_vec()),
+			AccountId::new([2; 32]),
+			None,
+			None,
+			founders,
+		)
+	}
+
+	#[test]
+	fn register_with_founders_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			// An unsold membership is reserved, a non-existing one is minted
+			assert_ok!(CommunitiesManager::create_memberships(
+				RuntimeOrigin::root(),
+				1,
+				10,
+				10,
+				TankConfig::default(),
+				None,
+			));
+
+			assert_ok!(register(RuntimeOrigin::root(), founders(vec![(3, 10, 2), (4, 11, 1)])));
+
+			let is_member_of = |who: u8| {
+				<Test as pallet_communities::Config>::MemberMgmt::is_member_of(&COMMUNITY, &AccountId::new([who; 32]))
+			};
+			assert!(is_member_of(3));
+			assert!(is_member_of(4));
+			let rank_of = |id: MembershipId| <Test as pallet_communities::Config>::MemberMgmt::rank_of(&COMMUNITY, &id);
+			assert_eq!(rank_of(10), Some(GenericRank::from(2)));
+			assert_eq!(rank_of(11), Some(GenericRank::from(1)));
+			System::assert_has_event(
+				crate::Event::<Test>::FoundingMemberAdded {
+					community_id: COMMUNITY,
+					who: AccountId::new([3; 32]),
+					membership_id: 10,
+					rank: GenericRank::from(2),
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn founders_pay_memberships_sharing_revenue_with_the_community() {
+		use frame_support::traits::fungible::{Inspect as _, Mutate as _};
+
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(CommunitiesManager::create_memberships(
+				RuntimeOrigin::root(),
+				1,
+				10,
+				100,
+				TankConfig::default(),
+				None,
+			));
+			assert_ok!(Balances::mint_into(&RemoteAccount::get(), 1_000));
+			let manager_balance = Balances::balance(&RootAccount::get());
+
+			assert_ok!(register(
+				RuntimeOrigin::signed(RemoteAccount::get()),
+				founders(vec![(3, 10, 1)])
+			));
+
+			// The community gets its share of the price, like when buying a
+			// membership
+			assert_eq!(Balances::balance(&Communities::community_account(&COMMUNITY)), 80);
+			// The manager gets the rest, besides the registration deposit
+			assert_eq!(Balances::balance(&RootAccount::get()), manager_balance + 10 + 20);
+		})
+	}
+
+	#[test]
+	fn register_fails_if_membership_is_taken() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CommunitiesManager::create_memberships(
+				RuntimeOrigin::root(),
+				1,
+				10,
+				10,
+				TankConfig::default(),
+				None,
+			));
+			// Someone else already bought it
+			assert_ok!(<Memberships as Transfer<AccountId>>::transfer(
+				&MembershipsManagerCollectionId::get(),
+				&10,
+				&AccountId::new([5; 32]),
+			));
+
+			assert_noop!(
+				register(RuntimeOrigin::root(), founders(vec![(3, 10, 1)])),
+				Error::<Test>::MembershipNotAvailable
+			);
+		})
+	}
+}
+
+mod track_templates {
+	use super::*;
+
+	fn track(name: &str, max_deciding: u32, approval_floor: u32) -> TrackInfo<Balance, BlockNumber> {
+		TrackInfo {
+			name: str_array(name),
+			max_deciding,
+			decision_deposit: 10,
+			prepare_period: 1,
+			decision_period: 100,
+			confirm_period: 10,
+			min_enactment_period: 1,
+			min_approval: Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(approval_floor),
+				ceil: Perbill::from_percent(100),
+			},
+			min_support: Curve::LinearDecreasing {
+				length: Perbill::from_percent(100),
+				floor: Perbill::from_percent(0),
+				ceil: Perbill::from_percent(50),
+			},
+		}
+	}
+
+	fn register(maybe_track: Option<CommunityTrack<TrackInfo<Balance, BlockNumber>>>) -> DispatchResult {
+		CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			COMMUNITY,
+			BoundedVec::truncate_from(b"Test Community".to_vec()),
+			AccountId::new([2; 32]),
+			None,
+			maybe_track,
+			Default::default(),
+		)
+	}
+
+	#[test]
+	fn set_track_template_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				CommunitiesManager::set_track_template(
+					RuntimeOrigin::signed(AccountId::new([1; 32])),
+					1,
+					Some(track("cooperative", 5, 50))
+				),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				CommunitiesManager::set_track_template(RuntimeOrigin::root(), 1, Some(track("cooperative", 0, 50))),
+				Error::<Test>::InvalidTrackInfo
+			);
+			assert_noop!(
+				CommunitiesManager::set_track_template(RuntimeOrigin::root(), 1, Some(track("cooperative", 5, 10))),
+				Error::<Test>::InvalidTrackInfo
+			);
+
+			assert_ok!(CommunitiesManager::set_track_template(
+				RuntimeOrigin::root(),
+				1,
+				Some(track("cooperative", 5, 50))
+			));
+			assert_eq!(TrackTemplates::<Test>::get(1).map(|t| t.max_deciding), Some(5));
+			System::assert_has_event(crate::Event::<Test>::TrackTemplateSet { id: 1 }.into());
+
+			assert_ok!(CommunitiesManager::set_track_template(RuntimeOrigin::root(), 1, None));
+			assert!(TrackTemplates::<Test>::get(1).is_none());
+			System::assert_has_event(crate::Event::<Test>::TrackTemplateRemoved { id: 1 }.into());
+
+			assert_noop!(
+				CommunitiesManager::set_track_template(RuntimeOrigin::root(), 1, None),
+				Error::<Test>::TrackTemplateNotFound
+			);
+		})
+	}
+
+	#[test]
+	fn register_with_template_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CommunitiesManager::set_track_template(
+				RuntimeOrigin::root(),
+				1,
+				Some(track("small DAO", 3, 60))
+			));
+
+			assert_noop!(
+				register(Some(CommunityTrack::Template(2))),
+				Error::<Test>::TrackTemplateNotFound
+			);
+			assert_ok!(register(Some(CommunityTrack::Template(1))));
+
+			let track_info = pallet_referenda_tracks::Tracks::<Test>::get(COMMUNITY).expect("track was created");
+			assert_eq!(track_info.name, str_array("Test Community"));
+			assert_eq!(track_info.max_deciding, 3);
+			assert_eq!(track_info.decision_deposit, 10);
+		})
+	}
+
+	#[test]
+	fn register_uses_default_template() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CommunitiesManager::set_track_template(
+				RuntimeOrigin::root(),
+				crate::DEFAULT_TRACK_TEMPLATE,
+				Some(track("token-holders", 10, 50))
+			));
+
+			assert_ok!(register(None));
+
+			let track_info = pallet_referenda_tracks::Tracks::<Test>::get(COMMUNITY).expect("track was created");
+			assert_eq!(track_info.name, str_array("Test Community"));
+			assert_eq!(track_info.max_deciding, 10);
+		})
+	}
+
+	#[test]
+	fn register_fails_without_default_template() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CommunitiesManager::set_track_template(
+				RuntimeOrigin::root(),
+				crate::DEFAULT_TRACK_TEMPLATE,
+				None
+			));
+			assert_noop!(register(None), Error::<Test>::TrackTemplateNotFound);
+		})
+	}
+
+	#[test]
+	fn initial_templates_are_seeded_by_migration() {
+		use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+		new_test_ext().execute_with(|| {
+			// A chain running before templates were seeded
+			let _ = TrackTemplates::<Test>::clear(u32::MAX, None);
+			StorageVersion::new(0).put::<CommunitiesManager>();
+
+			crate::migrations::v1::SeedTrackTemplates::<Test>::on_runtime_upgrade();
+			assert_eq!(
+				TrackTemplates::<Test>::get(crate::DEFAULT_TRACK_TEMPLATE),
+				InitialTrackTemplates::get()
+					.first()
+					.map(|(_, track_info)| track_info.clone())
+			);
+			assert_eq!(CommunitiesManager::on_chain_storage_version(), 1);
+
+			// Templates set before the upgrade are kept
+			StorageVersion::new(0).put::<CommunitiesManager>();
+			assert_ok!(CommunitiesManager::set_track_template(
+				RuntimeOrigin::root(),
+				crate::DEFAULT_TRACK_TEMPLATE,
+				Some(track("token-holders", 10, 50))
+			));
+			crate::migrations::v1::SeedTrackTemplates::<Test>::on_runtime_upgrade();
+			assert_eq!(
+				TrackTemplates::<Test>::get(crate::DEFAULT_TRACK_TEMPLATE).map(|t| t.max_deciding),
+				Some(10)
+			);
+		})
+	}
+
+	#[test]
+	fn register_validates_custom_tracks() {
+		new_test_ext().execute_with(|| {
+			let mut invalid = track("custom", 1, 50);
+			invalid.confirm_period = invalid.decision_period + 1;
+			assert_noop!(
+				register(Some(CommunityTrack::Custom(invalid))),
+				Error::<Test>::InvalidTrackInfo
+			);
+
+			assert_ok!(register(Some(CommunityTrack::Custom(track("custom", 2, 50)))));
+			assert_eq!(
+				pallet_referenda_tracks::Tracks::<Test>::get(COMMUNITY).map(|t| t.max_deciding),
+				Some(2)
+			);
+		})
+	}
+}
 
 #[test]
 fn create_membership_works() {
@@ -24,9 +502,629 @@ fn create_membership_works() {
 			config,
 			Some(BlockNumber::MAX),
 		));
+		assert_eq!(GasTankPeriodicity::<Test>::get(1), Some(7 * DAYS));
+	})
+}
+
+fn tier(amount: u16, rank: u8, metadata: &[u8], price: (u32, u64)) -> MembershipTier<Weight, BlockNumber, u32, u64> {
+	MembershipTier {
+		amount,
+		rank: GenericRank::from(rank),
+		tank_config: TankConfig::default(),
+		metadata: BoundedVec::truncate_from(metadata.to_vec()),
+		price,
+		maybe_expiration: None,
+	}
+}
+
+#[test]
+fn create_membership_tiers_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let collection_id = MembershipsManagerCollectionId::get();
+
+		assert_ok!(CommunitiesManager::create_membership_tiers(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(vec![
+				tier(2, 3, b"ipfs://gold", (1, 100)),
+				tier(3, 1, b"ipfs://basic", (1, 10)),
+			]),
+		));
+
+		// Memberships of the first tier
+		for id in 1..=2 {
+			assert_eq!(
+				Memberships::system_attribute(&collection_id, Some(&id), b"membership_member_rank"),
+				Some(GenericRank::from(3).encode())
+			);
+			assert_eq!(
+				pallet_nfts::ItemMetadataOf::<Test>::get(collection_id, id).map(|m| m.data.into_inner()),
+				Some(b"ipfs://gold".to_vec())
+			);
+			assert_eq!(MembershipPrices::<Test>::get(id), Some((1, 100)));
+		}
+
+		// Memberships of the second tier
+		for id in 3..=5 {
+			assert_eq!(
+				Memberships::system_attribute(&collection_id, Some(&id), b"membership_member_rank"),
+				Some(GenericRank::from(1).encode())
+			);
+			assert_eq!(MembershipPrices::<Test>::get(id), Some((1, 10)));
+		}
+		assert_eq!(MembershipPrices::<Test>::get(6), None);
+
+		System::assert_has_event(
+			crate::Event::<Test>::MembershipTierCreated {
+				starting_at: 3,
+				amount: 3,
+				rank: GenericRank::from(1),
+				price: (1, 10),
+			}
+			.into(),
+		);
 	})
 }
 
+#[test]
+fn create_membership_tiers_fails_if_exceeding_max_memberships() {
+	new_test_ext().execute_with(|| {
+		let amount = (MAX_TIERED_MEMBERSHIPS / 2 + 1) as u16;
+		assert_noop!(
+			CommunitiesManager::create_membership_tiers(
+				RuntimeOrigin::root(),
+				1,
+				BoundedVec::truncate_from(vec![tier(amount, 1, b"", (1, 10)), tier(amount, 2, b"", (1, 20))]),
+			),
+			Error::<Test>::CreatingTooManyMemberships
+		);
+	})
+}
+
+const COMMUNITY: CommunityId = 1;
+const ASSET: u32 = 1;
+
+fn setup_community_and_asset(buyer: &AccountId) {
+	System::set_block_number(1);
+	assert_ok!(CommunitiesManager::register(
+		RuntimeOrigin::root(),
+		COMMUNITY,
+		BoundedVec::truncate_from(b"Test Community".to_vec()),
+		AccountId::new([2; 32]),
+		None,
+		None,
+		Default::default(),
+	));
+	assert_ok!(Assets::force_create(
+		RuntimeOrigin::root(),
+		ASSET,
+		RootAccount::get(),
+		true,
+		1
+	));
+	assert_ok!(Assets::mint_into(ASSET, buyer, 1_000));
+}
+
+#[test]
+fn buy_membership_works() {
+	new_test_ext().execute_with(|| {
+		let buyer = AccountId::new([1; 32]);
+		setup_community_and_asset(&buyer);
+
+		assert_ok!(CommunitiesManager::create_membership_tiers(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(vec![tier(1, 2, b"ipfs://gold", (ASSET, 100))]),
+		));
+
+		// Not priced in an asset
+		assert_noop!(
+			CommunitiesManager::buy_membership(RuntimeOrigin::signed(buyer.clone()), COMMUNITY, 2),
+			Error::<Test>::MembershipNotForSale
+		);
+
+		assert_ok!(CommunitiesManager::buy_membership(
+			RuntimeOrigin::signed(buyer.clone()),
+			COMMUNITY,
+			1
+		));
+
+		let community_account = Communities::community_account(&COMMUNITY);
+		assert_eq!(Assets::balance(ASSET, &buyer), 900);
+		assert_eq!(Assets::balance(ASSET, &community_account), 80);
+		assert_eq!(Assets::balance(ASSET, &RootAccount::get()), 20);
+		assert!(<Test as pallet_communities::Config>::MemberMgmt::is_member_of(
+			&COMMUNITY, &buyer
+		));
+		System::assert_has_event(
+			crate::Event::<Test>::MembershipBought {
+				community_id: COMMUNITY,
+				membership_id: 1,
+				who: buyer.clone(),
+				price: (ASSET, 100),
+			}
+			.into(),
+		);
+
+		// Cannot be bought twice
+		assert_noop!(
+			CommunitiesManager::buy_membership(RuntimeOrigin::signed(buyer), COMMUNITY, 1),
+			Error::<Test>::MembershipNotForSale
+		);
+	})
+}
+
+#[test]
+fn renew_membership_works() {
+	new_test_ext().execute_with(|| {
+		let member = AccountId::new([1; 32]);
+		setup_community_and_asset(&member);
+		let community_origin: RuntimeOrigin = pallet_communities::Origin::<Test>::new(COMMUNITY).into();
+
+		assert_ok!(CommunitiesManager::create_membership_tiers(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(vec![tier(2, 1, b"", (ASSET, 100))]),
+		));
+		assert_ok!(CommunitiesManager::buy_membership(
+			RuntimeOrigin::signed(member.clone()),
+			COMMUNITY,
+			1
+		));
+		assert_ok!(CommunitiesManager::buy_membership(
+			RuntimeOrigin::signed(member.clone()),
+			COMMUNITY,
+			2
+		));
+		assert_ok!(Memberships::set_typed_attribute(
+			&COMMUNITY,
+			&1,
+			&b"membership_expiration",
+			&10u64
+		));
+
+		assert_noop!(
+			CommunitiesManager::renew_membership(RuntimeOrigin::signed(member.clone()), COMMUNITY, 1),
+			Error::<Test>::NoRenewalTerms
+		);
+
+		let terms = RenewalTerms {
+			price: (ASSET, 50),
+			period: 100,
+		};
+		assert_ok!(CommunitiesManager::set_renewal_terms(
+			community_origin,
+			Some(terms.clone())
+		));
+		assert_eq!(MembershipRenewalTerms::<Test>::get(COMMUNITY), Some(terms));
+
+		// Only the holder or the community can renew a membership
+		assert_noop!(
+			CommunitiesManager::renew_membership(RuntimeOrigin::signed(AccountId::new([3; 32])), COMMUNITY, 1),
+			Error::<Test>::NotMembershipHolder
+		);
+
+		// Membership 2 never expires
+		assert_noop!(
+			CommunitiesManager::renew_membership(RuntimeOrigin::signed(member.clone()), COMMUNITY, 2),
+			Error::<Test>::MembershipDoesNotExpire
+		);
+
+		// Renewing before the expiration extends it
+		System::set_block_number(5);
+		assert_ok!(CommunitiesManager::renew_membership(
+			RuntimeOrigin::signed(member.clone()),
+			COMMUNITY,
+			1
+		));
+		assert_eq!(
+			Memberships::typed_system_attribute::<_, u64>(&COMMUNITY, Some(&1), &b"membership_expiration"),
+			Some(110)
+		);
+
+		// Renewing after the expiration counts from now
+		System::set_block_number(200);
+		assert_ok!(CommunitiesManager::renew_membership(
+			RuntimeOrigin::signed(member.clone()),
+			COMMUNITY,
+			1
+		));
+		assert_eq!(
+			Memberships::typed_system_attribute::<_, u64>(&COMMUNITY, Some(&1), &b"membership_expiration"),
+			Some(300)
+		);
+		System::assert_has_event(
+			crate::Event::<Test>::MembershipRenewed {
+				community_id: COMMUNITY,
+				membership_id: 1,
+				expiration: 300,
+			}
+			.into(),
+		);
+
+		// Two purchases and two renewals
+		assert_eq!(Assets::balance(ASSET, &member), 1_000 - 200 - 100);
+
+		// The community can renew the memberships of its members too
+		let community_account = Communities::community_account(&COMMUNITY);
+		assert_ok!(Assets::mint_into(ASSET, &community_account, 1_000));
+		assert_ok!(CommunitiesManager::renew_membership(
+			RuntimeOrigin::signed(community_account),
+			COMMUNITY,
+			1
+		));
+		assert_eq!(
+			Memberships::typed_system_attribute::<_, u64>(&COMMUNITY, Some(&1), &b"membership_expiration"),
+			Some(400)
+		);
+	})
+}
+
+mod minting_jobs {
+	use super::*;
+
+	fn schedule(amount: u32) {
+		schedule_at(1, amount);
+	}
+
+	fn schedule_at(starting_at: MembershipId, amount: u32) {
+		assert_ok!(CommunitiesManager::schedule_minting(
+			RuntimeOrigin::root(),
+			amount,
+			starting_at,
+			10,
+			TankConfig::default(),
+			None,
+		));
+	}
+
+	fn exists(id: MembershipId) -> bool {
+		Memberships::owner(&MembershipsManagerCollectionId::get(), &id).is_some()
+	}
+
+	#[test]
+	fn continue_minting_mints_in_chunks() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let caller = RuntimeOrigin::signed(AccountId::new([1; 32]));
+			schedule(10);
+
+			assert_ok!(CommunitiesManager::continue_minting(caller.clone(), 0, 4));
+			assert!((1..=4).all(exists));
+			assert!(!exists(5));
+			assert_eq!(MintingJobs::<Test>::get(0).map(|job| job.remaining), Some(6));
+			System::assert_has_event(
+				crate::Event::<Test>::MintingJobProgressed {
+					job_id: 0,
+					minted: 4,
+					remaining: 6,
+				}
+				.into(),
+			);
+
+			assert_ok!(CommunitiesManager::continue_minting(caller.clone(), 0, 100));
+			assert!((1..=10).all(exists));
+			assert!(!exists(11));
+			assert_eq!(MintingJobs::<Test>::get(0), None);
+			System::assert_has_event(crate::Event::<Test>::MintingJobFinished { job_id: 0, minted: 10 }.into());
+
+			assert_noop!(
+				CommunitiesManager::continue_minting(caller, 0, 1),
+				Error::<Test>::MintingJobNotFound
+			);
+		})
+	}
+
+	#[test]
+	fn skips_memberships_that_already_exist() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(CommunitiesManager::create_memberships(
+				RuntimeOrigin::root(),
+				2,
+				3,
+				10,
+				TankConfig::default(),
+				None,
+			));
+			schedule(5);
+
+			assert_ok!(CommunitiesManager::continue_minting(
+				RuntimeOrigin::signed(AccountId::new([1; 32])),
+				0,
+				5
+			));
+			assert!((1..=5).all(exists));
+			assert!(!exists(6));
+			System::assert_has_event(crate::Event::<Test>::MintingJobFinished { job_id: 0, minted: 3 }.into());
+		})
+	}
+
+	#[test]
+	fn mints_on_idle() {
+		use frame_support::traits::Get;
+
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			schedule(20);
+
+			// Only the memberships actually minted are accounted for, not the whole
+			// chunk the block had room for
+			let weight = CommunitiesManager::on_idle(1, Weight::MAX);
+			let lookup_weight = <Test as frame_system::Config>::DbWeight::get().reads_writes(3, 1);
+			assert_eq!(
+				weight,
+				lookup_weight.saturating_add(<Test as crate::Config>::WeightInfo::create_memberships(20))
+			);
+			assert!((1..=20).all(exists));
+			assert_eq!(MintingJobs::<Test>::get(0), None);
+
+			// Cancelled jobs are not picked up
+			schedule(1);
+			assert_ok!(CommunitiesManager::cancel_minting(RuntimeOrigin::root(), 1));
+			CommunitiesManager::on_idle(2, Weight::MAX);
+			assert!(!exists(21));
+		})
+	}
+
+	#[test]
+	fn mints_on_idle_in_the_order_jobs_were_scheduled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			for starting_at in [1, 10, 20, 30] {
+				schedule_at(starting_at, 3);
+			}
+			assert_ok!(CommunitiesManager::cancel_minting(RuntimeOrigin::root(), 2));
+
+			for expected in [1, 10, 30] {
+				CommunitiesManager::on_idle(1, Weight::MAX);
+				assert!((expected..expected + 3).all(exists));
+			}
+			assert!(!exists(20));
+			assert_eq!(FirstPendingMintingJob::<Test>::get(), 3);
+		})
+	}
+
+	#[test]
+	fn failed_jobs_are_kept_for_continue_minting() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let collection = MembershipsManagerCollectionId::get();
+			let config = |max_supply| pallet_nfts::CollectionConfig {
+				settings: Default::default(),
+				max_supply,
+				mint_settings: Default::default(),
+			};
+			assert_ok!(Memberships::force_collection_config(
+				RuntimeOrigin::root(),
+				collection,
+				config(Some(3)),
+			));
+			schedule(5);
+			schedule_at(10, 2);
+
+			// The whole chunk is rolled back, leaving the job as it was
+			CommunitiesManager::on_idle(1, Weight::MAX);
+			assert!(!exists(1));
+			System::assert_has_event(
+				crate::Event::<Test>::MintingJobFailed {
+					job_id: 0,
+					remaining: 5,
+					error: pallet_nfts::Error::<Test>::MaxSupplyReached.into(),
+				}
+				.into(),
+			);
+			assert_eq!(MintingJobs::<Test>::get(0).map(|job| job.remaining), Some(5));
+
+			// The jobs scheduled after it are not held back
+			CommunitiesManager::on_idle(2, Weight::MAX);
+			assert!((10..=11).all(exists));
+			assert!(!exists(1));
+
+			assert_ok!(Memberships::force_collection_config(
+				RuntimeOrigin::root(),
+				collection,
+				config(None),
+			));
+			assert_ok!(CommunitiesManager::continue_minting(
+				RuntimeOrigin::signed(AccountId::new([1; 32])),
+				0,
+				5
+			));
+			assert!((1..=5).all(exists));
+			assert_eq!(MintingJobs::<Test>::get(0), None);
+		})
+	}
+
+	#[test]
+	fn does_not_mint_without_enough_weight() {
+		new_test_ext().execute_with(|| {
+			schedule(5);
+			CommunitiesManager::on_idle(1, Weight::zero());
+			assert!(!exists(1));
+			assert_eq!(MintingJobs::<Test>::get(0).map(|job| job.remaining), Some(5));
+		})
+	}
+
+	#[test]
+	fn cancel_minting_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			schedule(10);
+			assert_ok!(CommunitiesManager::continue_minting(
+				RuntimeOrigin::signed(AccountId::new([1; 32])),
+				0,
+				3
+			));
+
+			assert_noop!(
+				CommunitiesManager::cancel_minting(RuntimeOrigin::signed(AccountId::new([1; 32])), 0),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(CommunitiesManager::cancel_minting(RuntimeOrigin::root(), 0));
+			assert_eq!(MintingJobs::<Test>::get(0), None);
+			assert!((1..=3).all(exists));
+			System::assert_has_event(crate::Event::<Test>::MintingJobCancelled { job_id: 0, minted: 3 }.into());
+		})
+	}
+}
+
+mod member_gas_tanks {
+	use super::*;
+
+	const FILL: Weight = Weight::from_parts(1_000_000, 0);
+
+	fn setup() -> (AccountId, RuntimeOrigin) {
+		let member = AccountId::new([1; 32]);
+		setup_community_and_asset(&member);
+		assert_ok!(CommunitiesManager::create_membership_tiers(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(vec![tier(1, 1, b"", (ASSET, 100))]),
+		));
+		assert_ok!(CommunitiesManager::buy_membership(
+			RuntimeOrigin::signed(member.clone()),
+			COMMUNITY,
+			1
+		));
+		assert_ok!(Balances::force_set_balance(
+			RuntimeOrigin::root(),
+			Communities::community_account(&COMMUNITY),
+			10_000_000
+		));
+
+		(member, pallet_communities::Origin::<Test>::new(COMMUNITY).into())
+	}
+
+	fn community_balance() -> Balance {
+		Balances::free_balance(Communities::community_account(&COMMUNITY))
+	}
+
+	#[test]
+	fn fill_member_gas_tank_works() {
+		new_test_ext().execute_with(|| {
+			let (member, community_origin) = setup();
+
+			assert_noop!(
+				CommunitiesManager::fill_member_gas_tank(RuntimeOrigin::signed(member), 1, FILL),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				CommunitiesManager::fill_member_gas_tank(community_origin.clone(), 2, FILL),
+				Error::<Test>::UnknownMembership
+			);
+
+			// The community must be able to afford the gas, but isn't charged
+			// upfront
+			assert_noop!(
+				CommunitiesManager::fill_member_gas_tank(community_origin.clone(), 1, FILL * 20),
+				Error::<Test>::InsufficientFundsForGas
+			);
+			assert_ok!(CommunitiesManager::fill_member_gas_tank(community_origin, 1, FILL));
+			assert_eq!(community_balance(), 10_000_000);
+			assert!(Memberships::system_attribute(&COMMUNITY, Some(&1), b"membership_gas").is_some());
+			assert_eq!(
+				GasTankPayers::<Test>::get(1, COMMUNITY),
+				Some(Communities::community_account(&COMMUNITY))
+			);
+			System::assert_has_event(
+				crate::Event::<Test>::MemberGasTankFilled {
+					community_id: COMMUNITY,
+					membership_id: 1,
+					capacity: FILL,
+				}
+				.into(),
+			);
+		})
+	}
+
+	#[test]
+	fn filling_keeps_the_periodicity_of_the_tank() {
+		new_test_ext().execute_with(|| {
+			let (_, community_origin) = setup();
+			GasTankPeriodicity::<Test>::insert(1, 100);
+
+			assert_ok!(CommunitiesManager::fill_member_gas_tank(community_origin, 1, FILL));
+			assert_eq!(GasTankPeriodicity::<Test>::get(1), Some(100));
+		})
+	}
+
+	#[test]
+	fn tanks_set_by_the_treasury_have_no_payer() {
+		new_test_ext().execute_with(|| {
+			let (_, community_origin) = setup();
+			assert_ok!(CommunitiesManager::fill_member_gas_tank(community_origin, 1, FILL));
+
+			assert_ok!(CommunitiesManager::set_gas_tank(
+				RuntimeOrigin::root(),
+				COMMUNITY,
+				1,
+				TankConfig {
+					capacity: Some(FILL),
+					periodicity: None,
+				}
+			));
+			assert_eq!(GasTankPayers::<Test>::get(1, COMMUNITY), None);
+		})
+	}
+
+	#[test]
+	fn refills_are_removed_when_the_membership_is_released() {
+		new_test_ext().execute_with(|| {
+			let (member, community_origin) = setup();
+			assert_ok!(CommunitiesManager::set_member_gas_refills(
+				community_origin.clone(),
+				1,
+				Some((FILL, 10))
+			));
+
+			assert_ok!(CommunitiesManager::fill_member_gas_tank(
+				community_origin.clone(),
+				1,
+				FILL
+			));
+
+			assert_ok!(Communities::remove_member(community_origin, member, 1));
+			assert_eq!(MemberGasRefills::<Test>::get(1, COMMUNITY), None);
+			assert_eq!(GasTankPayers::<Test>::get(1, COMMUNITY), None);
+		})
+	}
+
+	#[test]
+	fn scheduled_refills_work() {
+		new_test_ext().execute_with(|| {
+			let (member, community_origin) = setup();
+			let refill = |who: &AccountId| {
+				CommunitiesManager::refill_member_gas_tank(RuntimeOrigin::signed(who.clone()), COMMUNITY, 1)
+			};
+
+			assert_noop!(refill(&member), Error::<Test>::NoGasRefills);
+
+			assert_ok!(CommunitiesManager::set_member_gas_refills(
+				community_origin.clone(),
+				1,
+				Some((FILL, 10))
+			));
+
+			// The first refill can be claimed right away, by anyone
+			assert_ok!(refill(&AccountId::new([9; 32])));
+			assert_noop!(refill(&member), Error::<Test>::GasRefillNotDue);
+
+			System::set_block_number(11);
+			assert_ok!(refill(&member));
+			assert_eq!(
+				MemberGasRefills::<Test>::get(1, COMMUNITY).map(|refill| refill.next_at),
+				Some(21)
+			);
+
+			assert_ok!(CommunitiesManager::set_member_gas_refills(community_origin, 1, None));
+			System::set_block_number(30);
+			assert_noop!(refill(&member), Error::<Test>::NoGasRefills);
+		})
+	}
+}
+
 #[test]
 fn weights() {
 	let max_total_extrinsics = MAX_BLOCK_WEIGHT * NORMAL_DISPATCH_RATIO;
@@ -43,12 +1141,27 @@ fn weights() {
 	let mut total = Weight::zero();
 
 	let calls = vec![
-		("register", SubstrateWeight::<Test>::register()),
+		("register", SubstrateWeight::<Test>::register(MAX_FOUNDERS)),
 		(
 			"create_memberships(1024)",
 			SubstrateWeight::<Test>::create_memberships(1024),
 		),
 		("set_gas_tank", SubstrateWeight::<Test>::set_gas_tank()),
+		(
+			"create_membership_tiers(512)",
+			SubstrateWeight::<Test>::create_membership_tiers(512),
+		),
+		("buy_membership", SubstrateWeight::<Test>::buy_membership()),
+		("set_renewal_terms", SubstrateWeight::<Test>::set_renewal_terms()),
+		("renew_membership", SubstrateWeight::<Test>::renew_membership()),
+		("schedule_minting", SubstrateWeight::<Test>::schedule_minting()),
+		("cancel_minting", SubstrateWeight::<Test>::cancel_minting()),
+		("set_track_template", SubstrateWeight::<Test>::set_track_template()),
+		("recall_memberships", SubstrateWeight::<Test>::recall_memberships(1024)),
+		(
+			"reprice_memberships",
+			SubstrateWeight::<Test>::reprice_memberships(1024),
+		),
 	];
 
 	for (function, weight) in calls {
@@ -7,9 +7,21 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for pallet_communities.
 pub trait WeightInfo {
-	fn register() -> Weight;
+	fn register(f: u32, ) -> Weight;
 	fn create_memberships(q: u32, ) -> Weight;
 	fn set_gas_tank() -> Weight;
+	fn create_membership_tiers(q: u32, ) -> Weight;
+	fn buy_membership() -> Weight;
+	fn set_renewal_terms() -> Weight;
+	fn renew_membership() -> Weight;
+	fn schedule_minting() -> Weight;
+	fn cancel_minting() -> Weight;
+	fn set_track_template() -> Weight;
+	fn recall_memberships(q: u32, ) -> Weight;
+	fn reprice_memberships(q: u32, ) -> Weight;
+	fn fill_member_gas_tank() -> Weight;
+	fn set_member_gas_refills() -> Weight;
+	fn refill_member_gas_tank() -> Weight;
 }
 
 /// Weights for pallet_communities using the Substrate node and recommended hardware.
@@ -43,7 +55,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `CommunityMemberships::CollectionConfigOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
 	/// Storage: `CommunityMemberships::CollectionAccount` (r:0 w:1)
 	/// Proof: `CommunityMemberships::CollectionAccount` (`max_values`: None, `max_size`: Some(66), added: 2541, mode: `MaxEncodedLen`)
-	fn register() -> Weight {
+	/// The range of component `f` is `[0, 16]`.
+	fn register(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `278`
 		//  Estimated: `132561`
@@ -52,6 +65,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(0, 132561))
 			.saturating_add(T::DbWeight::get().reads(8))
 			.saturating_add(T::DbWeight::get().writes(14))
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(f.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(f.into()))
 	}
 	/// Storage: `CommunityMemberships::Item` (r:1023 w:1023)
 	/// Proof: `CommunityMemberships::Item` (`max_values`: None, `max_size`: Some(859), added: 3334, mode: `MaxEncodedLen`)
@@ -88,6 +105,100 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+
+	/// Storage: `CommunityMemberships::ItemMetadataOf` (r:512 w:512)
+	/// Storage: `CommunityMemberships::Attribute` (r:1024 w:1024)
+	/// Storage: `CommunitiesManager::MembershipPrices` (r:0 w:512)
+	/// The range of component `q` is `[1, 512]`.
+	fn create_membership_tiers(q: u32, ) -> Weight {
+		Weight::from_parts(127_470_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(118_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 6900).saturating_mul(q.into()))
+	}
+
+	fn buy_membership() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 10240))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(12))
+	}
+
+	fn set_renewal_terms() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4096))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn renew_membership() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 10240))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+
+	fn schedule_minting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1024))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+
+	fn cancel_minting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1024))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_track_template() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3518))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// The range of component `q` is `[1, 1024]`.
+	fn recall_memberships(q: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(q.into()))
+	}
+	/// The range of component `q` is `[1, 1024]`.
+	fn reprice_memberships(q: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(q.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(q.into()))
+	}
+	fn fill_member_gas_tank() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn set_member_gas_refills() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4324))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn refill_member_gas_tank() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
 }
 
 impl WeightInfo for () {
@@ -119,7 +230,8 @@ impl WeightInfo for () {
 	/// Proof: `CommunityMemberships::CollectionConfigOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
 	/// Storage: `CommunityMemberships::CollectionAccount` (r:0 w:1)
 	/// Proof: `CommunityMemberships::CollectionAccount` (`max_values`: None, `max_size`: Some(66), added: 2541, mode: `MaxEncodedLen`)
-	fn register() -> Weight {
+	/// The range of component `f` is `[0, 16]`.
+	fn register(f: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `278`
 		//  Estimated: `132561`
@@ -128,6 +240,10 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(0, 132561))
 			.saturating_add(RocksDbWeight::get().reads(8))
 			.saturating_add(RocksDbWeight::get().writes(14))
+			.saturating_add(Weight::from_parts(45_000_000, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(f.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(f.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(f.into()))
 	}
 	/// Storage: `CommunityMemberships::Item` (r:1023 w:1023)
 	/// Proof: `CommunityMemberships::Item` (`max_values`: None, `max_size`: Some(859), added: 3334, mode: `MaxEncodedLen`)
@@ -164,4 +280,98 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2))
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+
+	/// Storage: `CommunityMemberships::ItemMetadataOf` (r:512 w:512)
+	/// Storage: `CommunityMemberships::Attribute` (r:1024 w:1024)
+	/// Storage: `CommunitiesManager::MembershipPrices` (r:0 w:512)
+	/// The range of component `q` is `[1, 512]`.
+	fn create_membership_tiers(q: u32, ) -> Weight {
+		Weight::from_parts(127_470_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(118_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 6900).saturating_mul(q.into()))
+	}
+
+	fn buy_membership() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 10240))
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(12))
+	}
+
+	fn set_renewal_terms() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4096))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+
+	fn renew_membership() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 10240))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+
+	fn schedule_minting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1024))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+
+	fn cancel_minting() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 1024))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_track_template() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3518))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// The range of component `q` is `[1, 1024]`.
+	fn recall_memberships(q: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(q.into()))
+	}
+	/// The range of component `q` is `[1, 1024]`.
+	fn reprice_memberships(q: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3547))
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(q.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(q.into())))
+			.saturating_add(Weight::from_parts(0, 3334).saturating_mul(q.into()))
+	}
+	fn fill_member_gas_tank() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn set_member_gas_refills() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4324))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn refill_member_gas_tank() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
 }
\ No newline at end of file
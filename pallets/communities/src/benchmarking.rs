@@ -13,7 +13,7 @@ use frame_benchmarking::v2::*;
 use frame_support::traits::{
 	fungible::{InspectFreeze, Mutate},
 	fungibles::Mutate as FunsMutate,
-	OriginTrait,
+	Get, OriginTrait,
 };
 use frame_system::{
 	pallet_prelude::{BlockNumberFor, OriginFor},
@@ -131,6 +131,24 @@ where
 	)
 }
 
+/// Creates a community whose admin is a signed origin that is to be recovered,
+/// returning its ID along with the lost and new admin origins.
+fn setup_admin_recovery<T: Config>(
+) -> Result<(CommunityIdOf<T>, PalletsOriginOf<T>, PalletsOriginOf<T>), BenchmarkError>
+where
+	OriginFor<T>: From<Origin<T>>,
+{
+	let (id, _, _, new_admin) = community_params::<T>(None);
+	let lost_admin_account: AccountIdOf<T> = frame_benchmarking::account("lost_admin", 0, 0);
+	let lost_admin: <T as Config>::RuntimeOrigin = RawOrigin::Signed(lost_admin_account).into();
+	let lost_admin: PalletsOriginOf<T> = lost_admin.into_caller();
+
+	Communities::<T>::create(RawOrigin::Root.into(), lost_admin.clone(), id)?;
+	crate::AdminRecoveryPeriod::<T>::insert(id, T::MinAdminRecoveryPeriod::get());
+
+	Ok((id, lost_admin, new_admin))
+}
+
 #[benchmarks(
 	where
 		OriginFor<T>: From<Origin<T>> + From<frame_system::Origin<T>>,
@@ -310,9 +328,10 @@ mod benchmarks {
 	#[benchmark]
 	fn vote() -> Result<(), BenchmarkError> {
 		// setup code
+		let asset_id = T::BenchmarkHelper::community_asset_id();
 		let (id, origin) = create_community::<T>(
 			RawOrigin::Root.into(),
-			Some(DecisionMethodFor::<T>::CommunityAsset(1u32.into(), 1u64.into())),
+			Some(DecisionMethodFor::<T>::CommunityAsset(asset_id.clone(), 1u64.into())),
 		)?;
 		let members = setup_members::<T>(origin.clone(), id)?;
 
@@ -321,7 +340,7 @@ mod benchmarks {
 			.expect("desired size of community to be equal or greather than 1")
 			.clone();
 
-		T::Assets::mint_into(1u32.into(), &who, 4u64.into())?;
+		T::Assets::mint_into(asset_id.clone(), &who, 4u64.into())?;
 
 		prepare_track_and_prepare_poll::<T>(origin.into_caller(), who.clone())?;
 
@@ -329,7 +348,7 @@ mod benchmarks {
 			RawOrigin::Signed(who.clone()).into(),
 			membership_id,
 			0u32,
-			Vote::AssetBalance(true, 1u32.into(), 1u64.into()),
+			Vote::AssetBalance(true, asset_id.clone(), 1u64.into()),
 		)?;
 
 		#[extrinsic_call]
@@ -337,7 +356,7 @@ mod benchmarks {
 			RawOrigin::Signed(who.clone()),
 			membership_id,
 			0u32,
-			Vote::AssetBalance(true, 1u32.into(), 2u64.into()),
+			Vote::AssetBalance(true, asset_id.clone(), 2u64.into()),
 		);
 
 		// verification code
@@ -345,7 +364,7 @@ mod benchmarks {
 			Event::VoteCasted {
 				who: who.clone(),
 				poll_index: 0u32,
-				vote: Vote::AssetBalance(true, 1u32.into(), 2u32.into()),
+				vote: Vote::AssetBalance(true, asset_id, 2u32.into()),
 			}
 			.into(),
 		);
@@ -448,6 +467,83 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_admin_recovery_period() -> Result<(), BenchmarkError> {
+		// setup code
+		let (id, origin) = create_community::<T>(RawOrigin::Root.into(), None)?;
+		let period = T::MinAdminRecoveryPeriod::get();
+
+		#[extrinsic_call]
+		_(origin.into_caller(), period);
+
+		// verification code
+		assert_has_event::<T>(Event::AdminRecoveryPeriodSet { id, period }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn propose_admin_recovery() -> Result<(), BenchmarkError> {
+		// setup code
+		let (id, lost_admin, new_admin) = setup_admin_recovery::<T>()?;
+		let origin = T::RecoveryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin, id, lost_admin.clone(), new_admin.clone());
+
+		// verification code
+		let executable_at = frame_system::Pallet::<T>::block_number() + T::MinAdminRecoveryPeriod::get();
+		assert_has_event::<T>(
+			Event::AdminRecoveryProposed {
+				id,
+				lost_admin,
+				new_admin,
+				executable_at,
+			}
+			.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn veto_admin_recovery() -> Result<(), BenchmarkError> {
+		// setup code
+		let (id, lost_admin, new_admin) = setup_admin_recovery::<T>()?;
+		let origin = T::RecoveryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Communities::<T>::propose_admin_recovery(origin, id, lost_admin, new_admin)?;
+		let (_, _, _, community_origin) = community_params::<T>(None);
+
+		#[extrinsic_call]
+		_(community_origin);
+
+		// verification code
+		assert_has_event::<T>(Event::AdminRecoveryVetoed { id }.into());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn execute_admin_recovery() -> Result<(), BenchmarkError> {
+		// setup code
+		let (id, lost_admin, new_admin) = setup_admin_recovery::<T>()?;
+		let origin = T::RecoveryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Communities::<T>::propose_admin_recovery(origin, id, lost_admin, new_admin.clone())?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + T::MinAdminRecoveryPeriod::get(),
+		);
+		let caller: AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), id);
+
+		// verification code
+		assert_eq!(CommunityIdFor::<T>::get(new_admin.clone()), Some(id));
+		assert_has_event::<T>(Event::AdminRecoveryExecuted { id, origin: new_admin }.into());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(
 		Communities,
 		sp_io::TestExternalities::new(Default::default()),
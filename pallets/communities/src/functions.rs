@@ -1,5 +1,5 @@
 use super::*;
-use fc_traits_memberships::{GenericRank, Inspect, Rank};
+use fc_traits_memberships::{GenericRank, Inspect, Manager, Rank};
 use frame_support::{
 	dispatch::PostDispatchInfo,
 	fail,
@@ -11,8 +11,9 @@ use frame_support::{
 		Polling,
 	},
 };
+use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::{
-	traits::{AccountIdConversion, Dispatchable},
+	traits::{AccountIdConversion, Dispatchable, Saturating},
 	DispatchResultWithInfo,
 };
 use sp_std::vec::Vec;
@@ -31,6 +32,26 @@ impl<T: Config> Pallet<T> {
 		T::MemberMgmt::is_member_of(community_id, who)
 	}
 
+	/// Whether the account holds at least one membership of the community
+	/// that hasn't expired yet.
+	pub fn is_active_member(community_id: &T::CommunityId, who: &AccountIdOf<T>) -> bool {
+		T::MemberMgmt::user_memberships(who, Some(*community_id)).any(|(_, m)| !Self::is_expired(community_id, &m))
+	}
+
+	pub fn is_expired(community_id: &T::CommunityId, m: &MembershipIdOf<T>) -> bool {
+		T::MembershipExpiry::expiration_of(community_id, m)
+			.is_some_and(|expiration| expiration < frame_system::Pallet::<T>::block_number())
+	}
+
+	/// Keeps track of a membership that has an expiration, so it's released
+	/// back to the community once it's been expired for longer than
+	/// [`ExpiredMembershipGracePeriod`][`Config::ExpiredMembershipGracePeriod`].
+	pub fn track_membership_expiry(community_id: &T::CommunityId, m: &MembershipIdOf<T>) {
+		if T::MembershipExpiry::expiration_of(community_id, m).is_some() {
+			ExpiringMemberships::<T>::insert(community_id, m, ());
+		}
+	}
+
 	pub fn member_rank(community_id: &T::CommunityId, m: &MembershipIdOf<T>) -> GenericRank {
 		T::MemberMgmt::rank_of(community_id, m).unwrap_or_default()
 	}
@@ -86,6 +107,10 @@ impl<T: Config> Pallet<T> {
 		T::Polls::try_access_poll(poll_index, |poll_status| {
 			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T>::NotOngoing)?;
 			ensure!(community_id == &class, Error::<T>::InvalidTrack);
+			ensure!(
+				!Self::is_expired(community_id, membership_id),
+				Error::<T>::MembershipExpired
+			);
 
 			let vote_multiplier = match CommunityDecisionMethod::<T>::get(community_id) {
 				DecisionMethod::Rank => T::MemberMgmt::rank_of(community_id, membership_id)
@@ -230,4 +255,59 @@ impl<T: Config> Tally<T> {
 			self.nays = self.nays.saturating_sub(multiplied_weight);
 		}
 	}
+
+	/// Releases back to their community the memberships that have been
+	/// expired for longer than the grace period, checking as many tracked
+	/// memberships as the given weight allows.
+	pub(crate) fn release_expired_memberships(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let weight_per_membership = T::WeightInfo::release_expired_membership();
+		let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+		if consumed.any_gt(limit) {
+			return Weight::zero();
+		}
+
+		let cursor = ExpiringMembershipsCursor::<T>::get();
+		let mut memberships = match cursor {
+			Some((community_id, membership_id)) => ExpiringMemberships::<T>::iter_keys_from(
+				ExpiringMemberships::<T>::hashed_key_for(community_id, membership_id),
+			),
+			None => ExpiringMemberships::<T>::iter_keys(),
+		};
+
+		let mut batch = Vec::new();
+		let mut next_cursor = None;
+		loop {
+			if consumed.saturating_add(weight_per_membership).any_gt(limit) {
+				next_cursor = batch.last().cloned().or(cursor);
+				break;
+			}
+			let Some(key) = memberships.next() else {
+				break;
+			};
+			consumed.saturating_accrue(weight_per_membership);
+			batch.push(key);
+		}
+		ExpiringMembershipsCursor::<T>::set(next_cursor);
+
+		let grace_period = T::ExpiredMembershipGracePeriod::get();
+		for (community_id, membership_id) in batch {
+			match T::MembershipExpiry::expiration_of(&community_id, &membership_id) {
+				Some(expiration) if expiration.saturating_add(grace_period) >= now => continue,
+				Some(_) => {
+					if T::MemberMgmt::release(&community_id, &membership_id).is_ok() {
+						T::OnMembershipReleased::on_membership_released(&community_id, &membership_id);
+						Self::deposit_event(Event::ExpiredMembershipReleased {
+							id: community_id,
+							membership_id,
+						});
+					}
+				}
+				// The membership is no longer set to expire
+				None => {}
+			}
+			ExpiringMemberships::<T>::remove(community_id, membership_id);
+		}
+
+		consumed
+	}
 }
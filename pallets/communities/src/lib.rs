@@ -146,8 +146,7 @@ pub mod pallet {
 		Blake2_128Concat, Parameter,
 	};
 	use frame_system::pallet_prelude::{ensure_signed, BlockNumberFor, OriginFor};
-	use sp_runtime::traits::AccountIdConversion;
-	use sp_runtime::traits::{Dispatchable, StaticLookup};
+	use sp_runtime::traits::{Dispatchable, Saturating, StaticLookup};
 	use sp_std::prelude::Box;
 
 	const ONE: NonZeroU8 = NonZeroU8::MIN;
@@ -206,6 +205,10 @@ pub mod pallet {
 			+ fungibles::Mutate<Self::AccountId>
 			+ fungibles::Create<Self::AccountId>;
 
+		/// Tells which community owns the namespace of an asset, so a
+		/// community can only create the asset it decides with within its own
+		type AssetNamespace: AssetNamespace<CommunityIdOf<Self>, AssetIdOf<Self>>;
+
 		/// Type allows for handling fungibles' freezes
 		type AssetsFreezer: fungibles::Inspect<Self::AccountId, Balance = NativeBalanceOf<Self>, AssetId = AssetIdOf<Self>>
 			+ fungibles::freeze::Inspect<Self::AccountId, Id = Self::RuntimeFreezeReason, AssetId = AssetIdOf<Self>>
@@ -240,6 +243,32 @@ pub mod pallet {
 		/// definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// Means to know when a membership expires
+		type MembershipExpiry: MembershipExpiry<CommunityIdOf<Self>, MembershipIdOf<Self>, BlockNumberFor<Self>>;
+
+		/// The amount of blocks an expired membership is kept by its holder
+		/// (so it can be renewed) before being released back to the community
+		#[pallet::constant]
+		type ExpiredMembershipGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// Hook called when a membership is released back to its community
+		type OnMembershipReleased: OnMembershipReleased<CommunityIdOf<Self>, MembershipIdOf<Self>>;
+
+		/// Origin that can propose to recover the admin origin of a community
+		/// that lost access to it
+		type RecoveryOrigin: EnsureOrigin<OriginFor<Self>>;
+
+		/// Origin of the community itself (e.g. a referendum of its members)
+		/// that can veto the recovery of its admin origin. It must not accept
+		/// the admin origins of the community, as the one being recovered
+		/// might be compromised.
+		type AdminRecoveryVetoOrigin: EnsureOrigin<OriginFor<Self>, Success = Self::CommunityId>;
+
+		/// The minimum amount of blocks a recovery of the admin origin has to
+		/// wait (so the community can veto it) before it can be executed.
+		#[pallet::constant]
+		type MinAdminRecoveryPeriod: Get<BlockNumberFor<Self>>;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 
@@ -293,6 +322,26 @@ pub mod pallet {
 	pub(super) type CommunityVoteLocks<T> =
 		StorageDoubleMap<_, Blake2_128Concat, AccountIdOf<T>, Blake2_128Concat, PollIndexOf<T>, VoteOf<T>>;
 
+	/// Memberships that have an expiration, so they can be released back to
+	/// the community once they've been expired for long enough.
+	#[pallet::storage]
+	pub(super) type ExpiringMemberships<T> =
+		StorageDoubleMap<_, Blake2_128Concat, CommunityIdOf<T>, Blake2_128Concat, MembershipIdOf<T>, ()>;
+
+	/// The last membership checked for expiration, so the next check resumes
+	/// from there.
+	#[pallet::storage]
+	pub(super) type ExpiringMembershipsCursor<T> = StorageValue<_, (CommunityIdOf<T>, MembershipIdOf<T>)>;
+
+	/// The time lock a community approved for recoveries of its admin origin,
+	/// during which the community can veto them.
+	#[pallet::storage]
+	pub(super) type AdminRecoveryPeriod<T> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, BlockNumberFor<T>>;
+
+	/// Recoveries of admin origins waiting for their time lock to end.
+	#[pallet::storage]
+	pub(super) type PendingAdminRecovery<T> = StorageMap<_, Blake2_128Concat, CommunityIdOf<T>, AdminRecoveryOf<T>>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -331,6 +380,35 @@ pub mod pallet {
 			who: AccountIdOf<T>,
 			poll_index: PollIndexOf<T>,
 		},
+		/// A membership that had been expired for longer than the grace period
+		/// has been released back to the community
+		ExpiredMembershipReleased {
+			id: T::CommunityId,
+			membership_id: MembershipIdOf<T>,
+		},
+		/// The community approved a new time lock for the recoveries of its
+		/// admin origin
+		AdminRecoveryPeriodSet {
+			id: T::CommunityId,
+			period: BlockNumberFor<T>,
+		},
+		/// A recovery of the admin origin of the community has been proposed,
+		/// and can be vetoed by the community until it's executable
+		AdminRecoveryProposed {
+			id: T::CommunityId,
+			lost_admin: PalletsOriginOf<T>,
+			new_admin: PalletsOriginOf<T>,
+			executable_at: BlockNumberFor<T>,
+		},
+		/// The community vetoed the pending recovery of its admin origin
+		AdminRecoveryVetoed {
+			id: T::CommunityId,
+		},
+		/// The admin origin of the community has been recovered
+		AdminRecoveryExecuted {
+			id: T::CommunityId,
+			origin: PalletsOriginOf<T>,
+		},
 	}
 
 	// Errors inform users that something worked or went wrong.
@@ -365,6 +443,33 @@ pub mod pallet {
 		AlreadyAdmin,
 		/// The vote is below the minimum requried
 		VoteBelowMinimum,
+		/// The membership has expired and must be renewed before it can be
+		/// used again
+		MembershipExpired,
+		/// The time lock is shorter than the minimum allowed for recoveries of
+		/// the admin origin
+		AdminRecoveryPeriodTooShort,
+		/// The origin to recover is not an admin origin of the community
+		NotAdminOrigin,
+		/// There's a recovery of the admin origin of the community already
+		/// pending
+		AdminRecoveryAlreadyPending,
+		/// There's no recovery of the admin origin of the community pending
+		NoAdminRecoveryPending,
+		/// The time lock of the recovery of the admin origin hasn't ended yet
+		AdminRecoveryNotDue,
+		/// The community hasn't approved a time lock for recoveries of its
+		/// admin origin
+		NoAdminRecoveryPeriod,
+		/// The asset to create is outside of the namespace of the community
+		AssetOutsideNamespace,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::release_expired_memberships(n, remaining_weight)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke
@@ -427,6 +532,7 @@ pub mod pallet {
 				.ok_or(Error::<T>::CommunityAtCapacity)?;
 
 			T::MemberMgmt::assign(&community_id, &membership_id, &who)?;
+			Self::track_membership_expiry(&community_id, &membership_id);
 
 			Self::deposit_event(Event::MemberAdded { who, membership_id });
 			Ok(())
@@ -450,6 +556,8 @@ pub mod pallet {
 			ensure!(T::MemberMgmt::is_member_of(&community_id, &who), Error::<T>::NotAMember);
 
 			T::MemberMgmt::release(&community_id, &membership_id)?;
+			ExpiringMemberships::<T>::remove(community_id, membership_id);
+			T::OnMembershipReleased::on_membership_released(&community_id, &membership_id);
 
 			Self::deposit_event(Event::MemberRemoved { who, membership_id });
 			Ok(())
@@ -483,22 +591,34 @@ pub mod pallet {
 
 		// === Governance ===
 
-		/// Decide the method used by the community to vote on proposals
+		/// Decide the method used by the community to vote on proposals. An
+		/// asset to decide with that doesn't exist yet is created, as long as
+		/// it's within the namespace of the community.
 		#[pallet::call_index(7)]
 		pub fn set_decision_method(
 			origin: OriginFor<T>,
 			community_id: T::CommunityId,
 			decision_method: DecisionMethodFor<T>,
 		) -> DispatchResult {
-			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::AdminOrigin::ensure_origin(origin)? == community_id,
+				DispatchError::BadOrigin
+			);
 			if let DecisionMethod::CommunityAsset(ref asset, min_vote) = decision_method {
-				// best effort attemt to create the asset if it doesn't exist
-				let _ = <T::Assets as fungibles::Create<T::AccountId>>::create(
-					asset.clone(),
-					T::PalletId::get().into_account_truncating(),
-					false,
-					min_vote,
-				);
+				// An asset that doesn't exist yet is created within the namespace of the
+				// community, owned by the community account
+				if !<T::Assets as fungibles::Inspect<T::AccountId>>::asset_exists(asset.clone()) {
+					ensure!(
+						T::AssetNamespace::community_of_asset(asset) == Some(community_id),
+						Error::<T>::AssetOutsideNamespace
+					);
+					<T::Assets as fungibles::Create<T::AccountId>>::create(
+						asset.clone(),
+						Self::community_account(&community_id),
+						false,
+						min_vote,
+					)?;
+				}
 			}
 			CommunityDecisionMethod::<T>::set(community_id, decision_method);
 			Self::deposit_event(Event::DecisionMethodSet { id: community_id });
@@ -586,5 +706,118 @@ pub mod pallet {
 		// crate::Origin::<T>::new(community_id); 	let post =
 		// call.dispatch(origin.into()).map_err(|e| e.error)?; 	Ok(post)
 		// }
+
+		// === Admin recovery ===
+
+		/// Sets the time lock the community approves for recoveries of its
+		/// admin origin. It can't be shorter than
+		/// [`MinAdminRecoveryPeriod`][`Config::MinAdminRecoveryPeriod`].
+		#[pallet::call_index(13)]
+		pub fn set_admin_recovery_period(origin: OriginFor<T>, period: BlockNumberFor<T>) -> DispatchResult {
+			let community_id = T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				period >= T::MinAdminRecoveryPeriod::get(),
+				Error::<T>::AdminRecoveryPeriodTooShort
+			);
+
+			AdminRecoveryPeriod::<T>::insert(community_id, period);
+
+			Self::deposit_event(Event::AdminRecoveryPeriodSet {
+				id: community_id,
+				period,
+			});
+			Ok(())
+		}
+
+		/// Proposes to replace an admin origin of the community that can no
+		/// longer act on its behalf. The recovery can be executed once the
+		/// time lock approved by the community ends, unless the community
+		/// vetoes it before. Communities that haven't approved a time lock
+		/// can't be recovered.
+		#[pallet::call_index(14)]
+		pub fn propose_admin_recovery(
+			origin: OriginFor<T>,
+			community_id: T::CommunityId,
+			lost_admin: PalletsOriginOf<T>,
+			new_admin: PalletsOriginOf<T>,
+		) -> DispatchResult {
+			T::RecoveryOrigin::ensure_origin(origin)?;
+			ensure!(Self::community_exists(&community_id), Error::<T>::CommunityDoesNotExist);
+			ensure!(
+				CommunityIdFor::<T>::get(&lost_admin) == Some(community_id),
+				Error::<T>::NotAdminOrigin
+			);
+			ensure!(!CommunityIdFor::<T>::contains_key(&new_admin), Error::<T>::AlreadyAdmin);
+			ensure!(
+				!PendingAdminRecovery::<T>::contains_key(community_id),
+				Error::<T>::AdminRecoveryAlreadyPending
+			);
+
+			let period = AdminRecoveryPeriod::<T>::get(community_id).ok_or(Error::<T>::NoAdminRecoveryPeriod)?;
+			let executable_at = frame_system::Pallet::<T>::block_number().saturating_add(period);
+
+			PendingAdminRecovery::<T>::insert(
+				community_id,
+				AdminRecovery {
+					lost_admin: lost_admin.clone(),
+					new_admin: new_admin.clone(),
+					executable_at,
+				},
+			);
+
+			Self::deposit_event(Event::AdminRecoveryProposed {
+				id: community_id,
+				lost_admin,
+				new_admin,
+				executable_at,
+			});
+			Ok(())
+		}
+
+		/// Vetoes the pending recovery of the admin origin of the community.
+		/// Only the community itself can veto it, not its admin origins.
+		#[pallet::call_index(15)]
+		pub fn veto_admin_recovery(origin: OriginFor<T>) -> DispatchResult {
+			let community_id = T::AdminRecoveryVetoOrigin::ensure_origin(origin)?;
+			PendingAdminRecovery::<T>::take(community_id).ok_or(Error::<T>::NoAdminRecoveryPending)?;
+
+			Self::deposit_event(Event::AdminRecoveryVetoed { id: community_id });
+			Ok(())
+		}
+
+		/// Replaces the lost admin origin of the community with the proposed
+		/// one, once the time lock of the recovery has ended. Can be called by
+		/// anyone.
+		#[pallet::call_index(16)]
+		pub fn execute_admin_recovery(origin: OriginFor<T>, community_id: T::CommunityId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let AdminRecovery {
+				lost_admin,
+				new_admin,
+				executable_at,
+			} = PendingAdminRecovery::<T>::get(community_id).ok_or(Error::<T>::NoAdminRecoveryPending)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= executable_at,
+				Error::<T>::AdminRecoveryNotDue
+			);
+			ensure!(!CommunityIdFor::<T>::contains_key(&new_admin), Error::<T>::AlreadyAdmin);
+
+			PendingAdminRecovery::<T>::remove(community_id);
+			// The lost admin might have been replaced in the meantime
+			if CommunityIdFor::<T>::get(&lost_admin) == Some(community_id) {
+				CommunityIdFor::<T>::remove(&lost_admin);
+			}
+			CommunityIdFor::<T>::insert(new_admin.clone(), community_id);
+
+			Self::deposit_event(Event::AdminOriginSet {
+				id: community_id,
+				origin: new_admin.clone(),
+			});
+			Self::deposit_event(Event::AdminRecoveryExecuted {
+				id: community_id,
+				origin: new_admin,
+			});
+			Ok(())
+		}
 	}
 }
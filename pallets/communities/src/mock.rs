@@ -24,8 +24,8 @@ pub use virto_common::{CommunityId, MembershipId};
 
 use crate::{
 	self as pallet_communities,
-	origin::{EnsureCommunity, EnsureSignedPays},
-	types::{Tally, VoteWeight},
+	origin::{EnsureCommunity, EnsureCommunityCollective, EnsureSignedPays},
+	types::{AssetNamespace, NonFungiblesMembershipExpiry, Tally, VoteWeight},
 	Config, DecisionMethod,
 };
 
@@ -417,16 +417,31 @@ type AnyoneElsePays = EnsureSignedPays<Test, ConstU64<10>, RootAccount>;
 
 pub type MembershipsManager = NonFungiblesMemberships<Nfts>;
 
+/// Each community owns the asset with its same ID
+pub struct AssetWithCommunityId;
+impl AssetNamespace<CommunityId, AssetId> for AssetWithCommunityId {
+	fn community_of_asset(asset_id: &AssetId) -> Option<CommunityId> {
+		CommunityId::try_from(*asset_id).ok()
+	}
+}
+
 impl Config for Test {
 	type PalletId = CommunitiesPalletId;
 	type CommunityId = CommunityId;
 	type MembershipId = MembershipId;
 
 	type Assets = Assets;
+	type AssetNamespace = AssetWithCommunityId;
 	type AssetsFreezer = AssetsFreezer;
 	type Balances = Balances;
 	type ItemConfig = pallet_nfts::ItemConfig;
 	type MemberMgmt = MembershipsManager;
+	type MembershipExpiry = NonFungiblesMembershipExpiry<Nfts, AccountId>;
+	type ExpiredMembershipGracePeriod = ConstU64<10>;
+	type OnMembershipReleased = ();
+	type RecoveryOrigin = EnsureRoot<AccountId>;
+	type AdminRecoveryVetoOrigin = EnsureCommunityCollective<Self>;
+	type MinAdminRecoveryPeriod = ConstU64<10>;
 	type Polls = Referenda;
 
 	type CreateOrigin = EitherOf<RootCreatesCommunitiesForFree, AnyoneElsePays>;
@@ -3,7 +3,7 @@ use crate::{
 	AccountIdOf, CommunityIdFor, Config, Info, Pallet,
 };
 use core::marker::PhantomData;
-use fc_traits_memberships::{GenericRank, Inspect};
+use fc_traits_memberships::GenericRank;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{EnsureOriginWithArg, MapSuccess, OriginTrait},
@@ -46,6 +46,34 @@ where
 	}
 }
 
+/// Ensures the origin is the voice of an active community itself (e.g. a
+/// referendum of its members), unlike [`EnsureCommunity`] which also
+/// accepts the admin origins set for the community.
+pub struct EnsureCommunityCollective<T>(PhantomData<T>);
+
+impl<T> EnsureOrigin<RuntimeOriginFor<T>> for EnsureCommunityCollective<T>
+where
+	RuntimeOriginFor<T>: OriginTrait + Into<Result<RawOrigin<T>, RuntimeOriginFor<T>>> + From<RawOrigin<T>>,
+	T: Config,
+{
+	type Success = T::CommunityId;
+
+	fn try_origin(o: RuntimeOriginFor<T>) -> Result<Self::Success, RuntimeOriginFor<T>> {
+		let Ok(RawOrigin { community_id: id, .. }) = o.clone().into() else {
+			return Err(o);
+		};
+		Info::<T>::get(id)
+			.and_then(|c| c.state.eq(&Active).then_some(id))
+			.ok_or(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOriginFor<T>, ()> {
+		use crate::BenchmarkHelper;
+		Ok(RawOrigin::new(T::BenchmarkHelper::community_id()).into())
+	}
+}
+
 morph_types! {
 	pub type PaymentForCreate<
 		AccountId,
@@ -76,7 +104,7 @@ where
 
 		match o.clone().into() {
 			Ok(Signed(who)) => {
-				if T::MemberMgmt::is_member_of(community_id, &who) {
+				if Pallet::<T>::is_active_member(community_id, &who) {
 					Ok(())
 				} else {
 					Err(o.clone())
@@ -244,6 +244,29 @@ mod vote {
 			});
 		}
 
+		#[test]
+		fn fails_if_membership_has_expired() {
+			new_test_ext().execute_with(|| {
+				use frame_support::traits::nonfungibles_v2::Mutate;
+				assert_ok!(Nfts::set_typed_attribute(
+					&COMMUNITY_A,
+					&membership(COMMUNITY_A, 1),
+					&b"membership_expiration",
+					&0u64
+				));
+
+				assert_noop!(
+					Communities::vote(
+						RuntimeOrigin::signed(ALICE),
+						membership(COMMUNITY_A, 1),
+						0,
+						Vote::Standard(true)
+					),
+					Error::MembershipExpired
+				);
+			});
+		}
+
 		#[test]
 		fn fails_if_voting_on_invalid_track() {
 			new_test_ext().execute_with(|| {
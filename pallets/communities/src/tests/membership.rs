@@ -188,3 +188,71 @@ mod member_rank {
 		}
 	}
 }
+
+mod expiration {
+	use super::*;
+	use frame_support::{
+		traits::{nonfungibles_v2::Mutate, Hooks},
+		weights::Weight,
+	};
+
+	fn set_expiration(membership_id: MembershipId, expiration: u64) {
+		assert_ok!(Nfts::set_typed_attribute(
+			&COMMUNITY,
+			&membership_id,
+			&b"membership_expiration",
+			&expiration
+		));
+		Communities::track_membership_expiry(&COMMUNITY, &membership_id);
+	}
+
+	#[test]
+	fn expired_members_are_not_active() {
+		new_test_ext(&[COMMUNITY_MEMBER_1], &[MEMBERSHIP_1]).execute_with(|| {
+			set_expiration(MEMBERSHIP_1, 5);
+			assert!(Communities::is_active_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+
+			System::set_block_number(6);
+			assert!(Communities::is_expired(&COMMUNITY, &MEMBERSHIP_1));
+			assert!(!Communities::is_active_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+			// Still holds the membership until it's released
+			assert!(Communities::is_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+		});
+	}
+
+	#[test]
+	fn releases_memberships_expired_past_grace_period() {
+		new_test_ext(&[COMMUNITY_MEMBER_1, COMMUNITY_MEMBER_2], &[MEMBERSHIP_1, MEMBERSHIP_2]).execute_with(|| {
+			set_expiration(MEMBERSHIP_1, 5);
+			set_expiration(MEMBERSHIP_2, 50);
+
+			// Within the grace period
+			System::set_block_number(15);
+			Communities::on_idle(15, Weight::MAX);
+			assert!(Communities::is_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+
+			System::set_block_number(16);
+			Communities::on_idle(16, Weight::MAX);
+			assert!(!Communities::is_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+			assert!(Communities::is_member(&COMMUNITY, &COMMUNITY_MEMBER_2));
+			System::assert_has_event(
+				crate::Event::<Test>::ExpiredMembershipReleased {
+					id: COMMUNITY,
+					membership_id: MEMBERSHIP_1,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn does_not_release_memberships_without_enough_weight() {
+		new_test_ext(&[COMMUNITY_MEMBER_1], &[MEMBERSHIP_1]).execute_with(|| {
+			set_expiration(MEMBERSHIP_1, 5);
+
+			System::set_block_number(100);
+			Communities::on_idle(100, Weight::zero());
+			assert!(Communities::is_member(&COMMUNITY, &COMMUNITY_MEMBER_1));
+		});
+	}
+}
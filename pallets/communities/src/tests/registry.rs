@@ -74,3 +74,234 @@ mod create {
 		});
 	}
 }
+
+mod set_decision_method {
+	use super::*;
+	use crate::DecisionMethod;
+	use frame_support::traits::fungibles::{roles::Inspect as _, Inspect};
+	use sp_runtime::DispatchError;
+
+	const COMMUNITY_B: CommunityId = 2;
+	const COMMUNITY_B_ORIGIN: OriginCaller = OriginCaller::Communities(crate::Origin::<Test>::new(COMMUNITY_B));
+
+	#[test]
+	fn fails_if_the_origin_is_not_the_admin_of_the_community() {
+		new_test_ext(&[], &[]).execute_with(|| {
+			assert_ok!(Communities::create(Root.into(), COMMUNITY_B_ORIGIN, COMMUNITY_B));
+
+			assert_noop!(
+				Communities::set_decision_method(COMMUNITY_B_ORIGIN.into(), COMMUNITY, DecisionMethod::Rank),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn creates_assets_only_within_the_namespace_of_the_community() {
+		new_test_ext(&[], &[]).execute_with(|| {
+			assert_noop!(
+				Communities::set_decision_method(
+					COMMUNITY_ORIGIN.into(),
+					COMMUNITY,
+					DecisionMethod::CommunityAsset(COMMUNITY_B.into(), 10)
+				),
+				Error::AssetOutsideNamespace
+			);
+			assert!(!Assets::asset_exists(COMMUNITY_B.into()));
+
+			assert_ok!(Communities::set_decision_method(
+				COMMUNITY_ORIGIN.into(),
+				COMMUNITY,
+				DecisionMethod::CommunityAsset(COMMUNITY.into(), 10)
+			));
+			assert_eq!(
+				Assets::owner(COMMUNITY.into()),
+				Some(Communities::community_account(&COMMUNITY))
+			);
+		});
+	}
+
+	#[test]
+	fn decides_with_existing_assets_of_any_namespace() {
+		new_test_ext(&[], &[]).execute_with(|| {
+			assert_ok!(Assets::force_create(
+				Root.into(),
+				COMMUNITY_B.into(),
+				Communities::community_account(&COMMUNITY_B),
+				true,
+				1
+			));
+
+			assert_ok!(Communities::set_decision_method(
+				COMMUNITY_ORIGIN.into(),
+				COMMUNITY,
+				DecisionMethod::CommunityAsset(COMMUNITY_B.into(), 10)
+			));
+		});
+	}
+}
+
+mod admin_recovery {
+	use super::*;
+	use crate::{AdminRecoveryPeriod, CommunityIdFor, PendingAdminRecovery};
+	use frame_support::traits::OriginTrait;
+	use sp_runtime::DispatchError;
+
+	const ALICE: AccountId = AccountId::new([1; 32]);
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	fn signed_caller(who: AccountId) -> OriginCaller {
+		RuntimeOrigin::signed(who).into_caller()
+	}
+
+	fn new_test_ext_with_signed_admin() -> sp_io::TestExternalities {
+		let mut ext = new_test_ext(&[], &[]);
+		ext.execute_with(|| {
+			assert_ok!(Communities::set_admin_origin(
+				COMMUNITY_ORIGIN.into(),
+				signed_caller(ALICE)
+			));
+		});
+		ext
+	}
+
+	#[test]
+	fn community_can_set_recovery_period() {
+		new_test_ext_with_signed_admin().execute_with(|| {
+			assert_noop!(
+				Communities::set_admin_recovery_period(COMMUNITY_ORIGIN.into(), 9),
+				Error::AdminRecoveryPeriodTooShort
+			);
+
+			assert_ok!(Communities::set_admin_recovery_period(COMMUNITY_ORIGIN.into(), 20));
+			assert_eq!(AdminRecoveryPeriod::<Test>::get(COMMUNITY), Some(20));
+			System::assert_has_event(
+				Event::AdminRecoveryPeriodSet {
+					id: COMMUNITY,
+					period: 20,
+				}
+				.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn fails_proposing_if_not_recovery_origin_or_not_admin() {
+		new_test_ext_with_signed_admin().execute_with(|| {
+			assert_noop!(
+				Communities::propose_admin_recovery(
+					RuntimeOrigin::signed(BOB),
+					COMMUNITY,
+					signed_caller(ALICE),
+					signed_caller(BOB)
+				),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Communities::propose_admin_recovery(Root.into(), COMMUNITY, signed_caller(BOB), signed_caller(BOB)),
+				Error::NotAdminOrigin
+			);
+			assert_noop!(
+				Communities::propose_admin_recovery(Root.into(), 99, signed_caller(ALICE), signed_caller(BOB)),
+				Error::CommunityDoesNotExist
+			);
+		});
+	}
+
+	#[test]
+	fn fails_proposing_if_the_community_has_not_approved_a_time_lock() {
+		new_test_ext_with_signed_admin().execute_with(|| {
+			assert_noop!(
+				Communities::propose_admin_recovery(Root.into(), COMMUNITY, signed_caller(ALICE), signed_caller(BOB)),
+				Error::NoAdminRecoveryPeriod
+			);
+		});
+	}
+
+	#[test]
+	fn recovery_can_be_executed_after_the_time_lock() {
+		new_test_ext_with_signed_admin().execute_with(|| {
+			assert_ok!(Communities::set_admin_recovery_period(COMMUNITY_ORIGIN.into(), 20));
+			assert_ok!(Communities::propose_admin_recovery(
+				Root.into(),
+				COMMUNITY,
+				signed_caller(ALICE),
+				signed_caller(BOB)
+			));
+			System::assert_has_event(
+				Event::AdminRecoveryProposed {
+					id: COMMUNITY,
+					lost_admin: signed_caller(ALICE),
+					new_admin: signed_caller(BOB),
+					executable_at: 21,
+				}
+				.into(),
+			);
+			assert_noop!(
+				Communities::propose_admin_recovery(Root.into(), COMMUNITY, signed_caller(ALICE), signed_caller(BOB)),
+				Error::AdminRecoveryAlreadyPending
+			);
+
+			System::set_block_number(20);
+			assert_noop!(
+				Communities::execute_admin_recovery(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::AdminRecoveryNotDue
+			);
+
+			System::set_block_number(21);
+			assert_ok!(Communities::execute_admin_recovery(
+				RuntimeOrigin::signed(BOB),
+				COMMUNITY
+			));
+			System::assert_has_event(
+				Event::AdminRecoveryExecuted {
+					id: COMMUNITY,
+					origin: signed_caller(BOB),
+				}
+				.into(),
+			);
+			assert_eq!(CommunityIdFor::<Test>::get(signed_caller(ALICE)), None);
+			assert_eq!(CommunityIdFor::<Test>::get(signed_caller(BOB)), Some(COMMUNITY));
+			assert!(!PendingAdminRecovery::<Test>::contains_key(COMMUNITY));
+
+			// The recovered admin can act on behalf of the community
+			assert_ok!(Communities::set_admin_origin(
+				RuntimeOrigin::signed(BOB),
+				signed_caller(ALICE)
+			));
+		});
+	}
+
+	#[test]
+	fn community_can_veto_recovery() {
+		new_test_ext_with_signed_admin().execute_with(|| {
+			assert_noop!(
+				Communities::veto_admin_recovery(COMMUNITY_ORIGIN.into()),
+				Error::NoAdminRecoveryPending
+			);
+			assert_ok!(Communities::set_admin_recovery_period(COMMUNITY_ORIGIN.into(), 10));
+			assert_ok!(Communities::propose_admin_recovery(
+				Root.into(),
+				COMMUNITY,
+				signed_caller(ALICE),
+				signed_caller(BOB)
+			));
+
+			// The admin being recovered can't veto it
+			assert_noop!(
+				Communities::veto_admin_recovery(RuntimeOrigin::signed(ALICE)),
+				DispatchError::BadOrigin
+			);
+
+			assert_ok!(Communities::veto_admin_recovery(COMMUNITY_ORIGIN.into()));
+			System::assert_has_event(Event::AdminRecoveryVetoed { id: COMMUNITY }.into());
+
+			System::set_block_number(11);
+			assert_noop!(
+				Communities::execute_admin_recovery(RuntimeOrigin::signed(BOB), COMMUNITY),
+				Error::NoAdminRecoveryPending
+			);
+			assert_eq!(CommunityIdFor::<Test>::get(signed_caller(ALICE)), Some(COMMUNITY));
+		});
+	}
+}
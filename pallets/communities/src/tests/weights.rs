@@ -31,6 +31,23 @@ fn weights() {
 		("remove_vote", SubstrateWeight::<Test>::remove_vote()),
 		("unlock", SubstrateWeight::<Test>::unlock()),
 		("dispatch_as_account", SubstrateWeight::<Test>::dispatch_as_account()),
+		(
+			"release_expired_membership",
+			SubstrateWeight::<Test>::release_expired_membership(),
+		),
+		(
+			"set_admin_recovery_period",
+			SubstrateWeight::<Test>::set_admin_recovery_period(),
+		),
+		(
+			"propose_admin_recovery",
+			SubstrateWeight::<Test>::propose_admin_recovery(),
+		),
+		("veto_admin_recovery", SubstrateWeight::<Test>::veto_admin_recovery()),
+		(
+			"execute_admin_recovery",
+			SubstrateWeight::<Test>::execute_admin_recovery(),
+		),
 	] {
 		println!("{function}: {weight:?}",);
 		println!(
@@ -4,7 +4,7 @@ use frame_support::pallet_prelude::*;
 use frame_support::traits::{
 	fungible::{self, Inspect as FunInspect},
 	fungibles::{self, Inspect as FunsInspect},
-	Polling,
+	nonfungibles_v2, Polling,
 };
 use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto};
 use sp_runtime::SaturatedConversion;
@@ -23,6 +23,7 @@ pub type PalletsOriginOf<T> =
 pub type MembershipIdOf<T> = <T as Config>::MembershipId;
 pub type RuntimeCallFor<T> = <T as Config>::RuntimeCall;
 pub type RuntimeOriginFor<T> = <T as Config>::RuntimeOrigin;
+pub type AdminRecoveryOf<T> = AdminRecovery<PalletsOriginOf<T>, frame_system::pallet_prelude::BlockNumberFor<T>>;
 
 #[cfg(feature = "runtime-benchmarks")]
 pub type BenchmarkHelperOf<T> = <T as Config>::BenchmarkHelper;
@@ -53,6 +54,15 @@ pub enum CommunityState {
 	Blocked,
 }
 
+/// A pending recovery of an admin origin of a community, that replaces the
+/// `lost_admin` with the `new_admin` once the `executable_at` block is reached.
+#[derive(Clone, Debug, Decode, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo)]
+pub struct AdminRecovery<PalletsOrigin, BlockNumber> {
+	pub lost_admin: PalletsOrigin,
+	pub new_admin: PalletsOrigin,
+	pub executable_at: BlockNumber,
+}
+
 /// The mechanism used by the community or one of its subsets to make decisions
 #[derive(Clone, Debug, Decode, Default, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo)]
 pub enum DecisionMethod<AssetId, MinVote> {
@@ -140,6 +150,53 @@ impl<T: Config> Tally<T> {
 	}
 }
 
+/// Means to know when a membership of a community expires, if it does at all.
+pub trait MembershipExpiry<CommunityId, MembershipId, BlockNumber> {
+	fn expiration_of(community_id: &CommunityId, membership_id: &MembershipId) -> Option<BlockNumber>;
+}
+
+impl<C, M, B> MembershipExpiry<C, M, B> for () {
+	fn expiration_of(_: &C, _: &M) -> Option<B> {
+		None
+	}
+}
+
+/// Means to know which community owns the namespace an asset belongs to, if
+/// any.
+pub trait AssetNamespace<CommunityId, AssetId> {
+	fn community_of_asset(asset_id: &AssetId) -> Option<CommunityId>;
+}
+
+impl<C, A> AssetNamespace<C, A> for () {
+	fn community_of_asset(_: &A) -> Option<C> {
+		None
+	}
+}
+
+/// Hook called when a membership is released back to its community, either
+/// because the member was removed or because it expired.
+pub trait OnMembershipReleased<CommunityId, MembershipId> {
+	fn on_membership_released(community_id: &CommunityId, membership_id: &MembershipId);
+}
+
+impl<C, M> OnMembershipReleased<C, M> for () {
+	fn on_membership_released(_: &C, _: &M) {}
+}
+
+/// Reads the expiration of a membership from the `membership_expiration`
+/// system attribute of the membership item.
+pub struct NonFungiblesMembershipExpiry<F, AccountId>(PhantomData<(F, AccountId)>);
+
+impl<F, AccountId, C, M, B> MembershipExpiry<C, M, B> for NonFungiblesMembershipExpiry<F, AccountId>
+where
+	F: nonfungibles_v2::Inspect<AccountId, CollectionId = C, ItemId = M>,
+	B: Decode,
+{
+	fn expiration_of(community_id: &C, membership_id: &M) -> Option<B> {
+		F::typed_system_attribute(community_id, Some(membership_id), &b"membership_expiration")
+	}
+}
+
 #[derive(PartialEq)]
 pub enum LockUpdateType {
 	Add,
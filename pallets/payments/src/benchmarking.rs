@@ -6,7 +6,7 @@ use frame_support::{
 	assert_ok,
 	traits::{
 		fungibles::{Inspect, Mutate},
-		Get,
+		Consideration, Get,
 	},
 	BoundedVec,
 };
@@ -30,6 +30,8 @@ fn create_accounts<T: Config>() -> (T::AccountId, T::AccountId, AccountIdLookupO
 	let beneficiary: T::AccountId = account("Bob", 0, 11);
 	let sender_lookup = T::Lookup::unlookup(sender.clone());
 	let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
+	T::Consideration::ensure_successful(&sender, Payments::<T>::deposit_footprint());
+	T::Consideration::ensure_successful(&beneficiary, Payments::<T>::deposit_footprint());
 
 	(sender, beneficiary, sender_lookup, beneficiary_lookup)
 }
@@ -110,6 +112,8 @@ mod benchmarks {
 			asset_id.clone(),
 			payment_amount,
 			order_remark.clone(),
+			Some(10u32.into()),
+			None,
 		);
 
 		assert_has_event!(
@@ -185,6 +189,147 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn submit_evidence() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, sender, beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		assert_ok!(Payments::<T>::request_refund(
+			RawOrigin::Signed(sender.clone()).into(),
+			payment_id
+		));
+		assert_ok!(Payments::<T>::dispute_refund(
+			RawOrigin::Signed(beneficiary.clone()).into(),
+			payment_id
+		));
+
+		// Worst case is updating the deposit of a party that already submitted
+		// evidence
+		let max_evidence = T::MaxEvidence::get() as usize;
+		T::Consideration::ensure_successful(
+			&beneficiary,
+			Footprint::from_parts(max_evidence, max_evidence * EvidenceOf::<T>::max_encoded_len()),
+		);
+		for _ in 1..max_evidence {
+			assert_ok!(Payments::<T>::submit_evidence(
+				RawOrigin::Signed(beneficiary.clone()).into(),
+				payment_id,
+				T::Hash::default(),
+				EvidenceKind::Statement,
+			));
+		}
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(beneficiary.clone()),
+			payment_id,
+			T::Hash::default(),
+			EvidenceKind::Document,
+		);
+
+		assert_has_event!(Event::PaymentEvidenceSubmitted { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pay_with_hash_lock(q: Linear<1, { T::MaxRemarkLength::get() }>) -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset_id: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset_id)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let hash = T::Hashing::hash(b"secret");
+		let order_remark: Option<BoundedDataOf<T>> = Some(BoundedVec::try_from(vec![1 as u8; q as usize]).unwrap());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(sender.clone()),
+			beneficiary_lookup,
+			asset_id,
+			amount,
+			hash,
+			10u32.into(),
+			order_remark,
+			None,
+		);
+
+		assert_has_event!(Event::PaymentHashLocked { hash: h, .. } if h == hash);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_payment() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset_id: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset_id)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let preimage: BoundedDataOf<T> = BoundedVec::truncate_from(vec![1u8; T::MaxRemarkLength::get() as usize]);
+
+		assert_ok!(Payments::<T>::pay_with_hash_lock(
+			RawOrigin::Signed(sender.clone()).into(),
+			beneficiary_lookup,
+			asset_id,
+			amount,
+			T::Hashing::hash(&preimage),
+			10u32.into(),
+			None,
+			None,
+		));
+		let (payment_id, _) = PaymentParties::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("payment was created"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(beneficiary.clone()), payment_id, preimage);
+
+		assert_has_event!(Event::PaymentClaimed { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn propose_partial_refund() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, sender, _beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sender.clone()), payment_id, Percent::from_percent(50));
+
+		assert_has_event!(Event::PartialRefundProposed { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_partial_refund() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, sender, beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		// Worst case is a payment whose refund was already requested
+		assert_ok!(Payments::<T>::request_refund(
+			RawOrigin::Signed(sender.clone()).into(),
+			payment_id
+		));
+		assert_ok!(Payments::<T>::propose_partial_refund(
+			RawOrigin::Signed(sender.clone()).into(),
+			payment_id,
+			Percent::from_percent(50),
+		));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(beneficiary.clone()),
+			payment_id,
+			Percent::from_percent(50),
+		);
+
+		assert_has_event!(Event::PartialRefundAccepted { .. });
+		Ok(())
+	}
+
 	#[benchmark]
 	fn resolve_dispute() -> Result<(), BenchmarkError> {
 		let amount = <BalanceOf<T>>::from(100000_u32);
@@ -214,16 +359,48 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn request_payment() -> Result<(), BenchmarkError> {
+	fn apply_default_dispute_result() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, sender, beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		assert_ok!(Payments::<T>::request_refund(
+			RawOrigin::Signed(sender.clone()).into(),
+			payment_id
+		));
+		assert_ok!(Payments::<T>::dispute_refund(
+			RawOrigin::Signed(beneficiary.clone()).into(),
+			payment_id
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, payment_id);
+
+		assert_has_event!(Event::PaymentDisputeResolved { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn request_payment(q: Linear<1, { T::MaxRemarkLength::get() }>) -> Result<(), BenchmarkError> {
 		let (sender, beneficiary, sender_lookup, _beneficiary_lookup) = create_accounts::<T>();
 		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
 		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
 		let amount = <BalanceOf<T>>::from(100000_u32);
+		let invoice: Option<BoundedDataOf<T>> = Some(BoundedVec::try_from(vec![1 as u8; q as usize]).unwrap());
 
 		#[extrinsic_call]
-		_(RawOrigin::Signed(beneficiary.clone()), sender_lookup, asset, amount);
+		_(
+			RawOrigin::Signed(beneficiary.clone()),
+			sender_lookup,
+			asset,
+			amount,
+			invoice.clone(),
+			Some(10u32.into()),
+			None,
+		);
 
-		assert_has_event!(Event::PaymentRequestCreated { .. });
+		assert_has_event!(Event::PaymentRequestCreated { remark, .. } if remark == invoice);
 		Ok(())
 	}
 
@@ -251,5 +428,265 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn pay_with_milestones(
+		q: Linear<1, { T::MaxRemarkLength::get() }>,
+		m: Linear<1, { T::MaxMilestones::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+
+		let asset_id: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset_id)?;
+		let milestone_amount = <BalanceOf<T>>::from(1000_u32);
+		let milestones: MilestoneSpecsOf<T> =
+			BoundedVec::truncate_from(vec![(milestone_amount, T::Hash::default()); m as usize]);
+		let order_remark: Option<BoundedDataOf<T>> = Some(BoundedVec::try_from(vec![1 as u8; q as usize]).unwrap());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(sender.clone()),
+			beneficiary_lookup,
+			asset_id.clone(),
+			milestones,
+			order_remark.clone(),
+			None,
+		);
+
+		let payment_amount = milestone_amount * <BalanceOf<T>>::from(m);
+		assert_has_event!(
+			Event::PaymentCreated { asset, amount, remark, .. }
+			if asset == asset_id && amount == payment_amount && remark == order_remark
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pay_with_splits(
+		q: Linear<1, { T::MaxRemarkLength::get() }>,
+		s: Linear<1, { T::MaxSplits::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+
+		let asset_id: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset_id)?;
+		let payment_amount = <BalanceOf<T>>::from(100000_u32);
+		let splits: SplitsOf<T> = BoundedVec::truncate_from(
+			(0..s)
+				.map(|i| (account("co-beneficiary", i, 12), Percent::from_percent(1)))
+				.collect(),
+		);
+		let order_remark: Option<BoundedDataOf<T>> = Some(BoundedVec::try_from(vec![1 as u8; q as usize]).unwrap());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(sender.clone()),
+			beneficiary_lookup,
+			asset_id.clone(),
+			payment_amount,
+			splits,
+			order_remark.clone(),
+			None,
+		);
+
+		assert_has_event!(
+			Event::PaymentCreated { asset, amount, remark, .. }
+			if asset == asset_id && amount == payment_amount && remark == order_remark
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn release_milestone() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let milestone_amount = <BalanceOf<T>>::from(50000_u32);
+		let milestones: MilestoneSpecsOf<T> =
+			BoundedVec::truncate_from(vec![(milestone_amount, T::Hash::default()); 2]);
+
+		assert_ok!(Payments::<T>::pay_with_milestones(
+			RawOrigin::Signed(sender.clone()).into(),
+			beneficiary_lookup,
+			asset,
+			milestones,
+			None
+		));
+		let (payment_id, _) = PaymentParties::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("payment was created"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sender), payment_id, 0);
+
+		assert_has_event!(Event::PaymentMilestoneReleased { milestone: 0, .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn subscribe() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(sender.clone()),
+			beneficiary_lookup,
+			asset,
+			amount,
+			10u32.into(),
+			12,
+		);
+
+		assert_has_event!(Event::SubscriptionCreated { periods: 12, .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pull_subscription() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+
+		assert_ok!(Payments::<T>::subscribe(
+			RawOrigin::Signed(sender).into(),
+			beneficiary_lookup,
+			asset,
+			amount,
+			10u32.into(),
+			12
+		));
+		let (subscription_id, subscription) = Subscriptions::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("subscription was created"))?;
+		frame_system::Pallet::<T>::set_block_number(subscription.next_pull_at);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(beneficiary), subscription_id);
+
+		assert_has_event!(Event::SubscriptionPulled { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_subscription() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, _, beneficiary_lookup) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+
+		assert_ok!(Payments::<T>::subscribe(
+			RawOrigin::Signed(sender.clone()).into(),
+			beneficiary_lookup,
+			asset,
+			amount,
+			10u32.into(),
+			12
+		));
+		let (subscription_id, _) = Subscriptions::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("subscription was created"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sender), subscription_id);
+
+		assert_has_event!(Event::SubscriptionCancelled { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reject_request() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, sender_lookup, _) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+
+		assert_ok!(Payments::<T>::request_payment(
+			RawOrigin::Signed(beneficiary).into(),
+			sender_lookup,
+			asset,
+			amount,
+			None,
+			None,
+			None
+		));
+		let (payment_id, _) = PaymentRequestExpiry::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("payment request was created"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sender), payment_id);
+
+		assert_has_event!(Event::PaymentRequestRejected { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_request() -> Result<(), BenchmarkError> {
+		let (sender, beneficiary, sender_lookup, _) = create_accounts::<T>();
+		let asset: AssetIdOf<T> = <AssetIdOf<T>>::default();
+		create_and_mint_asset::<T>(&sender, &beneficiary, &asset)?;
+		let amount = <BalanceOf<T>>::from(100000_u32);
+
+		assert_ok!(Payments::<T>::request_payment(
+			RawOrigin::Signed(beneficiary).into(),
+			sender_lookup,
+			asset,
+			amount,
+			None,
+			Some(10u32.into()),
+			None
+		));
+		let (payment_id, expiry) = PaymentRequestExpiry::<T>::iter()
+			.next()
+			.ok_or(BenchmarkError::Stop("payment request was created"))?;
+		frame_system::Pallet::<T>::set_block_number(expiry);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sender), payment_id);
+
+		assert_has_event!(Event::PaymentRequestExpired { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn grant_discount() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, _sender, beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(beneficiary.clone()),
+			payment_id,
+			<BalanceOf<T>>::from(1000_u32),
+		);
+
+		assert_has_event!(Event::PaymentDiscountGranted { .. });
+		Ok(())
+	}
+
+	#[benchmark]
+	fn prune_finished_payment() -> Result<(), BenchmarkError> {
+		let amount = <BalanceOf<T>>::from(100000_u32);
+		let asset = <AssetIdOf<T>>::default();
+		let (payment_id, sender, _beneficiary, _sender_lookup, _beneficiary_lookup) =
+			create_payment::<T>(&amount, &asset, None)?;
+
+		assert_ok!(Payments::<T>::release(RawOrigin::Signed(sender).into(), payment_id));
+		let prune_at = frame_system::Pallet::<T>::block_number().saturating_add(T::FinishedPaymentRetention::get());
+
+		#[block]
+		{
+			Payments::<T>::prune_finished_payments(prune_at, frame_support::weights::Weight::MAX);
+		}
+
+		assert_has_event!(Event::PaymentPruned { .. });
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Payments, crate::mock::new_test_ext(), crate::mock::Test);
 }
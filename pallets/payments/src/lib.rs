@@ -15,12 +15,16 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+pub mod runtime_api;
+
 pub use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use sp_io::hashing::blake2_256;
 
 use frame_support::{
 	dispatch::{GetDispatchInfo, PostDispatchInfo},
 	ensure, fail,
+	storage::with_storage_layer,
 	traits::{
 		fungibles::{
 			hold::Mutate as FunHoldMutate, Balanced as FunBalanced, Inspect as FunInspect, Mutate as FunMutate,
@@ -31,16 +35,18 @@ use frame_support::{
 			Fortitude::Polite,
 			Precision::Exact,
 			Preservation::{Expendable, Preserve},
+			Restriction::Free,
 		},
-		Bounded, CallerTrait, QueryPreimage, StorePreimage,
+		Bounded, CallerTrait, Consideration, EnsureOriginWithArg, Footprint, Get, QueryPreimage, StorePreimage,
 	},
+	weights::Weight,
 };
 use sp_std::vec::Vec;
 
 pub mod weights;
 use sp_runtime::{
-	traits::{CheckedAdd, Dispatchable, StaticLookup},
-	DispatchError, DispatchResult, Percent, Saturating,
+	traits::{CheckedAdd, Convert, Dispatchable, Hash as HashT, One, StaticLookup, Zero},
+	DispatchError, DispatchResult, Perbill, Percent, Saturating,
 };
 pub use weights::*;
 
@@ -85,7 +91,8 @@ pub mod pallet {
 			+ GetDispatchInfo
 			+ From<Call<Self>>;
 
-		/// Currency type that this works on.
+		/// Currency type that this works on. Payments in the native balance can
+		/// be made by combining it with the assets through `fungible::UnionOf`.
 		type Assets: FunInspect<Self::AccountId, Balance = Self::AssetsBalance>
 			+ FunMutate<Self::AccountId>
 			+ FunBalanced<Self::AccountId>
@@ -111,6 +118,24 @@ pub mod pallet {
 
 		type DisputeResolver: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
 
+		/// The result applied to disputes that aren't resolved within the
+		/// `DisputeReviewPeriod`. As nobody resolved them, the incentive isn't
+		/// paid to anyone and both parties get theirs back.
+		#[pallet::constant]
+		type DefaultDisputeResult: Get<DisputeResult>;
+
+		/// Who can be named to judge the disputes of a payment when it's
+		/// created (e.g. a community or a specific account)
+		type Arbiter: Parameter + MaxEncodedLen;
+
+		/// The origin of an arbiter, which yields the account the incentive of
+		/// the disputes it resolves is paid to
+		type ArbiterOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, Self::Arbiter, Success = Self::AccountId>;
+
+		/// The account of an arbiter, which can't be a party of the payments it
+		/// judges
+		type ArbiterAccount: Convert<Self::Arbiter, Self::AccountId>;
+
 		type PaymentId: PaymentId<Self> + Member + Parameter + MaxEncodedLen;
 
 		type Scheduler: ScheduleNamed<BlockNumberFor<Self>, CallOf<Self>, Self::PalletsOrigin, Hasher = Self::Hashing>;
@@ -121,6 +146,10 @@ pub mod pallet {
 		/// The overarching hold reason.
 		type RuntimeHoldReason: From<HoldReason>;
 
+		/// A means of holding a deposit from the creator of a payment for the
+		/// storage it uses while it's live.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -139,16 +168,47 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxDiscounts: Get<u32>;
 
+		/// Maximum amount of co-beneficiaries a payment can be split between
+		#[pallet::constant]
+		type MaxSplits: Get<u32>;
+
+		/// Maximum amount of pieces of evidence that can be submitted for a
+		/// payment under review
+		#[pallet::constant]
+		type MaxEvidence: Get<u32>;
+
+		/// Maximum amount of milestones a payment can be released in
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+
+		/// Number of blocks a payment request is kept if no expiry is given,
+		/// after which it's pruned
+		#[pallet::constant]
+		type PaymentRequestLifetime: Get<BlockNumberFor<Self>>;
+
 		/// Buffer period - number of blocks to wait before user can claim
 		/// canceled payment
 		#[pallet::constant]
 		type CancelBufferBlockLength: Get<BlockNumberFor<Self>>;
 
+		/// Number of blocks a disputed payment can be under review, after
+		/// which the `DefaultDisputeResult` is applied
+		#[pallet::constant]
+		type DisputeReviewPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Number of blocks a finished payment is kept before it's pruned
+		#[pallet::constant]
+		type FinishedPaymentRetention: Get<BlockNumberFor<Self>>;
+
 		#[cfg(feature = "runtime-benchmarks")]
 		type BenchmarkHelper: BenchmarkHelper<AccountIdOf<Self>, AssetIdOf<Self>, BalanceOf<Self>>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -180,6 +240,76 @@ pub mod pallet {
 		ResultQuery<Error<T>::NonExistentStorageValue>,
 	>;
 
+	#[pallet::storage]
+	/// Index of the payments an account is the beneficiary of, kept alongside
+	/// `PaymentParties`
+	pub type PaymentsByBeneficiary<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::PaymentId, ()>;
+
+	#[pallet::storage]
+	/// Milestones of payments released in stages. The amount of the payment
+	/// is reduced as milestones get released, so refunds and disputes only
+	/// involve the unreleased balance.
+	pub type PaymentMilestones<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, MilestonesOf<T>>;
+
+	#[pallet::storage]
+	/// Co-beneficiaries of payments whose amount is split, along with their
+	/// share. The beneficiary of the payment keeps the rest.
+	pub type PaymentSplits<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, SplitsOf<T>>;
+
+	#[pallet::storage]
+	/// Amount of payments created in the latest block any were created in.
+	/// It lets `PaymentId` implementations tell apart payments created in the
+	/// same block, or even the same extrinsic.
+	pub(super) type PaymentsInBlock<T: Config> = StorageValue<_, (BlockNumberFor<T>, u16)>;
+
+	#[pallet::storage]
+	/// The deposit held from the creator of a payment while it's live
+	pub type PaymentDeposits<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, (T::AccountId, T::Consideration)>;
+
+	#[pallet::storage]
+	/// Finished payments, keyed by the block from which they can be pruned
+	pub type FinishedPayments<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, T::PaymentId, ()>;
+
+	#[pallet::storage]
+	/// The earliest block whose finished payments might not have been pruned
+	/// yet
+	pub(super) type FinishedPaymentsCursor<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+	#[pallet::storage]
+	/// The arbiter named to resolve disputes of a payment, instead of the
+	/// `DisputeResolver`.
+	pub type PaymentArbiters<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, T::Arbiter>;
+
+	#[pallet::storage]
+	/// The condition for the beneficiary of a hash-locked payment to be paid
+	pub type PaymentHashLocks<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, HashLockOf<T>>;
+
+	#[pallet::storage]
+	/// Partial refunds proposed by a party of a payment, awaiting to be
+	/// accepted by the other one
+	pub type RefundProposals<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, RefundProposal>;
+
+	#[pallet::storage]
+	/// Evidence submitted by the parties of a payment under review
+	pub type PaymentEvidence<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, EvidenceListOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	/// The deposit held from each party of a payment under review for the
+	/// evidence it submitted
+	pub type EvidenceDeposits<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PaymentId, Blake2_128Concat, T::AccountId, T::Consideration>;
+
+	#[pallet::storage]
+	/// The block at which an outstanding payment request expires
+	pub type PaymentRequestExpiry<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, BlockNumberFor<T>>;
+
+	#[pallet::storage]
+	/// Subscriptions created by senders, which are pulled every period by a
+	/// named task of the scheduler until there are no periods left.
+	pub type Subscriptions<T: Config> = StorageMap<_, Twox64Concat, T::PaymentId, SubscriptionOf<T>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -189,6 +319,7 @@ pub mod pallet {
 			asset: AssetIdOf<T>,
 			amount: BalanceOf<T>,
 			remark: Option<BoundedDataOf<T>>,
+			arbiter: Option<T::Arbiter>,
 		},
 		/// Payment amount released to the recipient
 		PaymentReleased { payment_id: T::PaymentId },
@@ -207,13 +338,108 @@ pub mod pallet {
 		/// the payment was refunded
 		PaymentRefunded { payment_id: T::PaymentId },
 		/// the refund request from creator was disputed by recipient
-		PaymentRefundDisputed { payment_id: T::PaymentId },
+		PaymentRefundDisputed {
+			payment_id: T::PaymentId,
+			review_deadline: BlockNumberFor<T>,
+		},
+		/// A party of a payment under review submitted a piece of evidence
+		PaymentEvidenceSubmitted {
+			payment_id: T::PaymentId,
+			who: T::AccountId,
+			hash: T::Hash,
+			kind: EvidenceKind,
+		},
 		/// Payment request was created by recipient
-		PaymentRequestCreated { payment_id: T::PaymentId },
+		PaymentRequestCreated {
+			payment_id: T::PaymentId,
+			remark: Option<BoundedDataOf<T>>,
+			expiry: BlockNumberFor<T>,
+		},
+		/// Payment request was rejected by sender
+		PaymentRequestRejected { payment_id: T::PaymentId },
+		/// Payment request was cancelled by recipient
+		PaymentRequestCancelled { payment_id: T::PaymentId },
+		/// Payment request was pruned after its expiry
+		PaymentRequestExpired { payment_id: T::PaymentId },
 		/// Payment request was completed by sender
 		PaymentRequestCompleted { payment_id: T::PaymentId },
+		/// A finished payment was removed from storage
+		PaymentPruned { payment_id: T::PaymentId },
+		/// Part of the amount of the payment was discounted by the recipient
+		PaymentDiscountGranted {
+			payment_id: T::PaymentId,
+			amount: BalanceOf<T>,
+		},
 		/// Payment disputed resolved
 		PaymentDisputeResolved { payment_id: T::PaymentId },
+		/// The payment will be released to the recipient at the given block
+		/// unless the creator requests a refund before
+		PaymentAutoReleaseScheduled {
+			payment_id: T::PaymentId,
+			release_at: BlockNumberFor<T>,
+		},
+		/// A subscription has been created by the sender
+		SubscriptionCreated {
+			subscription_id: T::PaymentId,
+			beneficiary: T::AccountId,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+			periods: u32,
+		},
+		/// The payment of a period of the subscription was created, to be
+		/// released at the end of the period
+		SubscriptionPulled {
+			subscription_id: T::PaymentId,
+			payment_id: T::PaymentId,
+			amount: BalanceOf<T>,
+		},
+		/// The payment of a period of the subscription could not be made (e.g.
+		/// the sender didn't have enough funds), so the period was missed
+		SubscriptionPullFailed {
+			subscription_id: T::PaymentId,
+			error: DispatchError,
+		},
+		/// The subscription was cancelled by the sender
+		SubscriptionCancelled { subscription_id: T::PaymentId },
+		/// All the periods of the subscription have been pulled
+		SubscriptionFinished { subscription_id: T::PaymentId },
+		/// The payment can be claimed by the recipient revealing the preimage
+		/// of the hash before the timeout, after which it's refunded
+		PaymentHashLocked {
+			payment_id: T::PaymentId,
+			hash: T::Hash,
+			timeout: BlockNumberFor<T>,
+		},
+		/// The recipient revealed the preimage of the hash lock of the payment
+		/// and got paid
+		PaymentClaimed {
+			payment_id: T::PaymentId,
+			preimage: BoundedDataOf<T>,
+		},
+		/// A party of the payment proposed to settle it, refunding part of it
+		PartialRefundProposed {
+			payment_id: T::PaymentId,
+			proposed_by: Role,
+			beneficiary_share: Percent,
+		},
+		/// The other party accepted the partial refund, settling the payment
+		PartialRefundAccepted {
+			payment_id: T::PaymentId,
+			refund: BalanceOf<T>,
+		},
+		/// The share of a co-beneficiary of the payment was paid out
+		PaymentSplitSettled {
+			payment_id: T::PaymentId,
+			beneficiary: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A milestone of the payment was released to the recipient
+		PaymentMilestoneReleased {
+			payment_id: T::PaymentId,
+			milestone: u32,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -246,12 +472,49 @@ pub mod pallet {
 		NoPaymentIdAvailable,
 		/// Call from wrong beneficiary
 		InvalidBeneficiary,
+		/// The milestone does not exist or has no amount
+		InvalidMilestone,
+		/// The split is empty, has shares of nothing or leaves nothing to the
+		/// beneficiary
+		InvalidSplit,
+		/// The milestone was already released
+		MilestoneAlreadyReleased,
+		/// The subscription does not exist or has no periods
+		InvalidSubscription,
+		/// The next period of the subscription can't be pulled yet
+		SubscriptionPullNotDue,
+		/// The expiry of the payment request has already passed
+		PaymentRequestExpired,
+		/// The payment request hasn't expired yet
+		PaymentRequestNotExpired,
+		/// The payment can't have any more discounts
+		TooManyDiscounts,
+		/// The payment can't have any more evidence submitted
+		TooMuchEvidence,
+		/// The preimage doesn't match the hash lock of the payment
+		InvalidPreimage,
+		/// The timeout of the hash lock of the payment has passed
+		HashLockExpired,
+		/// There's no partial refund proposed for the payment with the given
+		/// share
+		InvalidRefundProposal,
+		/// The arbiter of a payment can't be its sender or its beneficiary
+		InvalidArbiter,
 	}
 
 	#[pallet::composite_enum]
 	pub enum HoldReason {
 		#[codec(index = 0)]
 		TransferPayment,
+		#[codec(index = 1)]
+		PaymentDeposit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_finished_payments(n, remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -262,6 +525,10 @@ pub mod pallet {
 		/// the option to add a remark, this remark can then be used to run
 		/// custom logic and trigger alternate payment flows. the specified
 		/// amount.
+		///
+		/// Optionally, the payment can be released automatically to the
+		/// recipient `auto_release_after` the given amount of blocks, unless the
+		/// creator requests a refund before.
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T as Config>::WeightInfo::pay(remark.as_ref().map(|x| x.len() as u32).unwrap_or(0)))]
 		pub fn pay(
@@ -270,6 +537,8 @@ pub mod pallet {
 			asset: AssetIdOf<T>,
 			#[pallet::compact] amount: BalanceOf<T>,
 			remark: Option<BoundedDataOf<T>>,
+			auto_release_after: Option<BlockNumberFor<T>>,
+			arbiter: Option<T::Arbiter>,
 		) -> DispatchResultWithPostInfo {
 			let sender = T::SenderOrigin::ensure_origin(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
@@ -285,15 +554,24 @@ pub mod pallet {
 				remark.as_ref().map(|x| x.as_slice()),
 			)?;
 
+			let amount = payment_detail.amount;
+			let beneficiary = payment_detail.beneficiary.clone();
 			// reserve funds for payment
 			Self::reserve_payment_amount(&sender, payment_detail)?;
+			Self::set_arbiter(payment_id, &sender, &beneficiary, arbiter.clone())?;
 			// emit paymentcreated event
 			Self::deposit_event(Event::PaymentCreated {
 				payment_id,
 				asset,
 				amount,
 				remark,
+				arbiter,
 			});
+
+			if let Some(delay) = auto_release_after {
+				let release_at = Self::schedule_auto_release(&sender, payment_id, delay)?;
+				Self::deposit_event(Event::PaymentAutoReleaseScheduled { payment_id, release_at });
+			}
 			Ok(().into())
 		}
 
@@ -308,6 +586,7 @@ pub mod pallet {
 			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
 			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
 			Self::settle_payment(&sender, &payment.beneficiary, &payment_id, None)?;
+			Self::cancel_auto_release(payment_id);
 
 			Self::deposit_event(Event::PaymentReleased { payment_id });
 			Ok(().into())
@@ -326,6 +605,11 @@ pub mod pallet {
 				let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
 				// refunds only possible for payments in created state
 				ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+				// hash-locked payments are only refunded after their timeout
+				ensure!(
+					!PaymentHashLocks::<T>::contains_key(payment_id),
+					Error::<T>::InvalidAction
+				);
 
 				// set the payment to requested refund
 				let current_block = frame_system::Pallet::<T>::block_number();
@@ -344,6 +628,7 @@ pub mod pallet {
 				)?;
 
 				payment.state = PaymentState::RefundRequested { cancel_block };
+				Self::cancel_auto_release(payment_id);
 
 				Ok(cancel_block)
 			})?;
@@ -358,9 +643,17 @@ pub mod pallet {
 		pub fn accept_and_pay(origin: OriginFor<T>, payment_id: T::PaymentId) -> DispatchResultWithPostInfo {
 			let sender = T::SenderOrigin::ensure_origin(origin)?;
 			let (_, beneficiary) = PaymentParties::<T>::get(&payment_id)?;
+			ensure!(
+				!Self::is_request_expired(&payment_id),
+				Error::<T>::PaymentRequestExpired
+			);
 
 			Payment::<T>::try_mutate(&sender, payment_id, |maybe_payment| -> Result<_, DispatchError> {
 				let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
+				ensure!(
+					payment.state == PaymentState::PaymentRequested,
+					Error::<T>::InvalidAction
+				);
 				const IS_DISPUTE: bool = false;
 
 				// Release sender fees recipients
@@ -383,14 +676,314 @@ pub mod pallet {
 				payment.state = PaymentState::Finished;
 				Ok(())
 			})?;
+			Self::clear_request_expiry(payment_id);
+			Self::finish_payment(payment_id)?;
 
 			Self::deposit_event(Event::PaymentRequestCompleted { payment_id });
 			Ok(().into())
 		}
 
+		/// Creates a payment like [`pay`](Pallet::pay), whose amount is the sum
+		/// of the given milestones. Each milestone (an amount and the hash of
+		/// its description) can be released on its own by the sender via
+		/// [`release_milestone`](Pallet::release_milestone).
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::pay_with_milestones(
+			remark.as_ref().map(|x| x.len() as u32).unwrap_or(0),
+			milestones.len() as u32,
+		))]
+		pub fn pay_with_milestones(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			milestones: MilestoneSpecsOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			arbiter: Option<T::Arbiter>,
+		) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			ensure!(!milestones.is_empty(), Error::<T>::InvalidMilestone);
+			let amount = milestones
+				.iter()
+				.try_fold(BalanceOf::<T>::zero(), |total, (amount, _)| {
+					ensure!(!amount.is_zero(), Error::<T>::InvalidMilestone);
+					total.checked_add(amount).ok_or(Error::<T>::MathError)
+				})?;
+
+			let (payment_id, payment_detail) = Self::create_payment(
+				&sender,
+				beneficiary.clone(),
+				asset.clone(),
+				amount,
+				PaymentState::Created,
+				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+			// Discounts on the amount can't be split across the milestones
+			ensure!(payment_detail.amount == amount, Error::<T>::InvalidMilestone);
+
+			Self::reserve_payment_amount(&sender, payment_detail)?;
+			Self::set_arbiter(payment_id, &sender, &beneficiary, arbiter.clone())?;
+			PaymentMilestones::<T>::insert(
+				payment_id,
+				MilestonesOf::<T>::truncate_from(
+					milestones
+						.into_iter()
+						.map(|(amount, description)| Milestone {
+							amount,
+							description,
+							released: false,
+						})
+						.collect(),
+				),
+			);
+
+			Self::deposit_event(Event::PaymentCreated {
+				payment_id,
+				asset,
+				amount,
+				remark,
+				arbiter,
+			});
+			Ok(().into())
+		}
+
+		/// Release a milestone of a payment created with milestones. The
+		/// fees and incentive are settled in proportion to the released
+		/// amount, and the payment is finished once all of its milestones are
+		/// released.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::release_milestone())]
+		pub fn release_milestone(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			milestone: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+
+			let mut payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+
+			let mut milestones = PaymentMilestones::<T>::get(payment_id).ok_or(Error::<T>::InvalidMilestone)?;
+			let released_milestone = milestones
+				.get_mut(milestone as usize)
+				.ok_or(Error::<T>::InvalidMilestone)?;
+			ensure!(!released_milestone.released, Error::<T>::MilestoneAlreadyReleased);
+			released_milestone.released = true;
+			let amount = released_milestone.amount;
+
+			Self::settle_partially(&sender, &payment_id, &mut payment, amount)?;
+
+			if milestones.iter().all(|m| m.released) {
+				payment.state = PaymentState::Finished;
+				PaymentMilestones::<T>::remove(payment_id);
+				PaymentSplits::<T>::remove(payment_id);
+				Self::finish_payment(payment_id)?;
+			} else {
+				PaymentMilestones::<T>::insert(payment_id, milestones);
+			}
+			let is_finished = payment.state == PaymentState::Finished;
+			Payment::<T>::insert(&sender, payment_id, payment);
+
+			Self::deposit_event(Event::PaymentMilestoneReleased {
+				payment_id,
+				milestone,
+				amount,
+			});
+			if is_finished {
+				Self::deposit_event(Event::PaymentReleased { payment_id });
+			}
+			Ok(().into())
+		}
+
+		/// Creates a payment like [`pay`](Pallet::pay), whose amount is split
+		/// between the beneficiary and the given co-beneficiaries (e.g. the
+		/// platform and the referrer of a purchase). When the payment is
+		/// settled, each co-beneficiary is paid its share of what the
+		/// beneficiary gets after paying the fees charged to it, and the
+		/// beneficiary keeps the rest.
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as Config>::WeightInfo::pay_with_splits(
+			remark.as_ref().map(|x| x.len() as u32).unwrap_or(0),
+			splits.len() as u32,
+		))]
+		pub fn pay_with_splits(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			splits: SplitsOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			arbiter: Option<T::Arbiter>,
+		) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			ensure!(!splits.is_empty(), Error::<T>::InvalidSplit);
+			let total_share = splits.iter().try_fold(Percent::zero(), |total, (_, share)| {
+				ensure!(!share.is_zero(), Error::<T>::InvalidSplit);
+				Ok::<_, Error<T>>(total.saturating_add(*share))
+			})?;
+			ensure!(total_share < Percent::one(), Error::<T>::InvalidSplit);
+
+			let (payment_id, payment_detail) = Self::create_payment(
+				&sender,
+				beneficiary.clone(),
+				asset.clone(),
+				amount,
+				PaymentState::Created,
+				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+
+			Self::reserve_payment_amount(&sender, payment_detail)?;
+			Self::set_arbiter(payment_id, &sender, &beneficiary, arbiter.clone())?;
+			PaymentSplits::<T>::insert(payment_id, splits);
+
+			Self::deposit_event(Event::PaymentCreated {
+				payment_id,
+				asset,
+				amount,
+				remark,
+				arbiter,
+			});
+			Ok(().into())
+		}
+
+		/// Authorizes a beneficiary to be paid the given amount every `period`
+		/// blocks, for the given amount of `periods`, starting on the next
+		/// block. Each period is pulled by a named task of the scheduler,
+		/// creating a regular payment that is held in escrow for the period
+		/// and released automatically at its end, unless the sender requests
+		/// a refund (and so opens the way to a dispute) before.
+		///
+		/// The sender pays a deposit for the storage of the subscription,
+		/// given back once it's finished or cancelled. Subscriptions take
+		/// their IDs from the same space as payments.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::subscribe())]
+		pub fn subscribe(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+			periods: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				!amount.is_zero() && !period.is_zero() && periods > 0,
+				Error::<T>::InvalidSubscription
+			);
+
+			let subscription_id = T::PaymentId::next(&sender, &beneficiary).ok_or(Error::<T>::NoPaymentIdAvailable)?;
+			ensure!(
+				!Subscriptions::<T>::contains_key(subscription_id)
+					&& !PaymentParties::<T>::contains_key(subscription_id),
+				Error::<T>::PaymentAlreadyInProcess
+			);
+			Self::take_deposit(
+				&sender,
+				subscription_id,
+				Footprint::from_mel::<(SubscriptionOf<T>, T::PaymentId)>(),
+			)?;
+
+			let first_pull_at = frame_system::Pallet::<T>::block_number()
+				.checked_add(&One::one())
+				.ok_or(Error::<T>::MathError)?;
+			let pull_call = <T as Config>::RuntimeCall::from(pallet::Call::<T>::pull_subscription { subscription_id });
+			T::Scheduler::schedule_named(
+				("subscription", subscription_id).using_encoded(blake2_256),
+				DispatchTime::At(first_pull_at),
+				Some((period, periods)),
+				63,
+				frame_system::RawOrigin::Signed(beneficiary.clone()).into(),
+				T::Preimages::bound(pull_call)?,
+			)?;
+
+			Subscriptions::<T>::insert(
+				subscription_id,
+				Subscription {
+					sender,
+					beneficiary: beneficiary.clone(),
+					asset: asset.clone(),
+					amount,
+					period,
+					remaining_periods: periods,
+					next_pull_at: first_pull_at,
+				},
+			);
+
+			Self::deposit_event(Event::SubscriptionCreated {
+				subscription_id,
+				beneficiary,
+				asset,
+				amount,
+				period,
+				periods,
+			});
+			Ok(().into())
+		}
+
+		/// Pulls the payment of the current period of a subscription. This is
+		/// called by the scheduler on behalf of the beneficiary, who can also
+		/// call it once the period is due. If the payment can't be made, the
+		/// period is missed and reported with an event.
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::pull_subscription())]
+		pub fn pull_subscription(origin: OriginFor<T>, subscription_id: T::PaymentId) -> DispatchResultWithPostInfo {
+			let beneficiary = T::BeneficiaryOrigin::ensure_origin(origin)?;
+			let mut subscription = Subscriptions::<T>::get(subscription_id).ok_or(Error::<T>::InvalidSubscription)?;
+			ensure!(beneficiary == subscription.beneficiary, Error::<T>::InvalidBeneficiary);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= subscription.next_pull_at,
+				Error::<T>::SubscriptionPullNotDue
+			);
+
+			match with_storage_layer(|| Self::escrow_subscription_period(&subscription)) {
+				Ok(payment_id) => Self::deposit_event(Event::SubscriptionPulled {
+					subscription_id,
+					payment_id,
+					amount: subscription.amount,
+				}),
+				Err(error) => Self::deposit_event(Event::SubscriptionPullFailed { subscription_id, error }),
+			}
+
+			subscription.remaining_periods.saturating_dec();
+			subscription.next_pull_at.saturating_accrue(subscription.period);
+			if subscription.remaining_periods.is_zero() {
+				Subscriptions::<T>::remove(subscription_id);
+				Self::release_deposit(subscription_id)?;
+				Self::deposit_event(Event::SubscriptionFinished { subscription_id });
+			} else {
+				Subscriptions::<T>::insert(subscription_id, subscription);
+			}
+			Ok(().into())
+		}
+
+		/// Cancels a subscription, so no further periods are pulled. The
+		/// payments of the periods already pulled are not affected.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_subscription())]
+		pub fn cancel_subscription(origin: OriginFor<T>, subscription_id: T::PaymentId) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+			let subscription = Subscriptions::<T>::get(subscription_id).ok_or(Error::<T>::InvalidSubscription)?;
+			ensure!(sender == subscription.sender, Error::<T>::InvalidAction);
+
+			Subscriptions::<T>::remove(subscription_id);
+			Self::release_deposit(subscription_id)?;
+			let _ = T::Scheduler::cancel_named(("subscription", subscription_id).using_encoded(blake2_256));
+
+			Self::deposit_event(Event::SubscriptionCancelled { subscription_id });
+			Ok(().into())
+		}
+
 		/// Cancel a payment in created state, this will release the reserved
 		/// back to creator of the payment. This extrinsic can only be called by
-		/// the recipient of the payment
+		/// the recipient of the payment, who can also use it to withdraw an
+		/// outstanding payment request
 		#[pallet::call_index(10)]
 		#[pallet::weight(<T as Config>::WeightInfo::cancel())]
 		pub fn cancel(origin: OriginFor<T>, payment_id: T::PaymentId) -> DispatchResultWithPostInfo {
@@ -409,11 +1002,15 @@ pub mod pallet {
 					Self::cancel_payment(&sender, payment)?;
 					Self::deposit_event(Event::PaymentRefunded { payment_id });
 				}
+				PaymentState::PaymentRequested => {
+					Self::clear_request_expiry(payment_id);
+					Self::deposit_event(Event::PaymentRequestCancelled { payment_id });
+				}
 				_ => fail!(Error::<T>::InvalidAction),
 			}
 
-			Payment::<T>::remove(&sender, &payment_id);
-			PaymentParties::<T>::remove(payment_id);
+			Self::remove_payment(&sender, payment_id)?;
+			Self::cancel_auto_release(payment_id);
 
 			Ok(().into())
 		}
@@ -429,30 +1026,54 @@ pub mod pallet {
 			let (sender, b) = PaymentParties::<T>::get(&payment_id)?;
 			ensure!(beneficiary == b, Error::<T>::InvalidBeneficiary);
 
-			Payment::<T>::try_mutate(&sender, &payment_id, |maybe_payment| -> Result<_, DispatchError> {
-				// ensure the payment exists
-				let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
-
-				// ensure the payment is in Requested Refund state
-				let PaymentState::RefundRequested { cancel_block } = payment.state else {
-					fail!(Error::<T>::InvalidAction);
-				};
-				ensure!(
-					cancel_block > frame_system::Pallet::<T>::block_number(),
-					Error::<T>::InvalidAction
-				);
-
-				// Hold beneficiary incentive amount to balance the incentives at the time to
-				// resolve the dispute
-				let reason = &HoldReason::TransferPayment.into();
-				T::Assets::hold(payment.asset.clone(), reason, &beneficiary, payment.incentive_amount)?;
-
-				payment.state = PaymentState::NeedsReview;
-
-				T::Scheduler::cancel_named(("payment", payment_id).using_encoded(blake2_256))
-			})?;
-
-			Self::deposit_event(Event::PaymentRefundDisputed { payment_id });
+			let review_deadline =
+				Payment::<T>::try_mutate(&sender, &payment_id, |maybe_payment| -> Result<_, DispatchError> {
+					// ensure the payment exists
+					let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
+
+					// ensure the payment is in Requested Refund state
+					let PaymentState::RefundRequested { cancel_block } = payment.state else {
+						fail!(Error::<T>::InvalidAction);
+					};
+					ensure!(
+						cancel_block > frame_system::Pallet::<T>::block_number(),
+						Error::<T>::InvalidAction
+					);
+
+					// Hold beneficiary incentive amount to balance the incentives at the time to
+					// resolve the dispute
+					let reason = &HoldReason::TransferPayment.into();
+					T::Assets::hold(payment.asset.clone(), reason, &beneficiary, payment.incentive_amount)?;
+
+					payment.state = PaymentState::NeedsReview;
+
+					T::Scheduler::cancel_named(("payment", payment_id).using_encoded(blake2_256))?;
+
+					// Apply the default result if the dispute isn't resolved in time
+					let review_deadline = frame_system::Pallet::<T>::block_number()
+						.checked_add(&T::DisputeReviewPeriod::get())
+						.ok_or(Error::<T>::MathError)?;
+					let resolve_call =
+						<T as Config>::RuntimeCall::from(pallet::Call::<T>::apply_default_dispute_result {
+							payment_id,
+						});
+
+					T::Scheduler::schedule_named(
+						("payment_review", payment_id).using_encoded(blake2_256),
+						DispatchTime::At(review_deadline),
+						None,
+						63,
+						frame_system::RawOrigin::Root.into(),
+						T::Preimages::bound(resolve_call)?,
+					)?;
+
+					Ok(review_deadline)
+				})?;
+
+			Self::deposit_event(Event::PaymentRefundDisputed {
+				payment_id,
+				review_deadline,
+			});
 			Ok(().into())
 		}
 
@@ -461,95 +1082,839 @@ pub mod pallet {
 		// using the `accept_and_pay` extrinsic.  The payment will be in
 		// PaymentRequested State and can only be modified by the `accept_and_pay`
 		// extrinsic.
+		//
+		// The request can carry a remark (e.g. an invoice hash), and is pruned
+		// `expires_after` the given amount of blocks (or the configured
+		// `PaymentRequestLifetime`) unless it's accepted before.
 		#[pallet::call_index(12)]
-		#[pallet::weight(<T as Config>::WeightInfo::request_payment())]
+		#[pallet::weight(<T as Config>::WeightInfo::request_payment(
+			remark.as_ref().map(|x| x.len() as u32).unwrap_or(0)
+		))]
 		pub fn request_payment(
 			origin: OriginFor<T>,
 			sender: AccountIdLookupOf<T>,
 			asset: AssetIdOf<T>,
 			#[pallet::compact] amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			expires_after: Option<BlockNumberFor<T>>,
+			arbiter: Option<T::Arbiter>,
 		) -> DispatchResultWithPostInfo {
 			let beneficiary = T::BeneficiaryOrigin::ensure_origin(origin)?;
 			let sender = T::Lookup::lookup(sender)?;
 			// create PaymentDetail and add to storage
 			let (payment_id, _) = Self::create_payment(
 				&sender,
-				beneficiary,
+				beneficiary.clone(),
 				asset,
 				amount,
 				PaymentState::PaymentRequested,
 				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+			Self::set_arbiter(payment_id, &sender, &beneficiary, arbiter)?;
+
+			let expiry = frame_system::Pallet::<T>::block_number()
+				.checked_add(&expires_after.unwrap_or_else(T::PaymentRequestLifetime::get))
+				.ok_or(Error::<T>::MathError)?;
+			let expire_call = <T as Config>::RuntimeCall::from(pallet::Call::<T>::expire_request { payment_id });
+			T::Scheduler::schedule_named(
+				("payment_request", payment_id).using_encoded(blake2_256),
+				DispatchTime::At(expiry),
 				None,
+				63,
+				frame_system::RawOrigin::Signed(beneficiary).into(),
+				T::Preimages::bound(expire_call)?,
 			)?;
+			PaymentRequestExpiry::<T>::insert(payment_id, expiry);
 
-			Self::deposit_event(Event::PaymentRequestCreated { payment_id });
+			Self::deposit_event(Event::PaymentRequestCreated {
+				payment_id,
+				remark,
+				expiry,
+			});
 
 			Ok(().into())
 		}
 
-		#[pallet::call_index(20)]
-		#[pallet::weight(<T as Config>::WeightInfo::resolve_dispute())]
-		pub fn resolve_dispute(
-			origin: OriginFor<T>,
-			payment_id: T::PaymentId,
-			dispute_result: DisputeResult,
-		) -> DispatchResultWithPostInfo {
-			let dispute_resolver = T::DisputeResolver::ensure_origin(origin)?;
-			let (sender, beneficiary) = PaymentParties::<T>::get(&payment_id)?;
-
+		/// Allows the sender of a payment request to reject it, removing it
+		/// from storage.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::reject_request())]
+		pub fn reject_request(origin: OriginFor<T>, payment_id: T::PaymentId) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
 			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
-			ensure!(payment.state == PaymentState::NeedsReview, Error::<T>::InvalidAction);
+			ensure!(
+				payment.state == PaymentState::PaymentRequested,
+				Error::<T>::InvalidAction
+			);
 
-			let dispute = Some((dispute_result, dispute_resolver));
-			Self::settle_payment(&sender, &beneficiary, &payment_id, dispute)?;
+			Self::remove_request(&sender, payment_id)?;
 
-			Self::deposit_event(Event::PaymentDisputeResolved { payment_id });
+			Self::deposit_event(Event::PaymentRequestRejected { payment_id });
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// The function will create a new payment. The fee and incentive
-	/// amounts will be calculated and the `PaymentDetail` will be added to
-	/// storage.
-	#[allow(clippy::too_many_arguments)]
-	fn create_payment(
-		sender: &T::AccountId,
-		beneficiary: T::AccountId,
-		asset: AssetIdOf<T>,
-		amount: BalanceOf<T>,
-		payment_state: PaymentState<BlockNumberFor<T>>,
-		incentive_percentage: Percent,
-		remark: Option<&[u8]>,
-	) -> Result<(T::PaymentId, PaymentDetail<T>), DispatchError> {
-		let payment_id = T::PaymentId::next(sender, &beneficiary).ok_or(Error::<T>::NoPaymentIdAvailable)?;
-		Payment::<T>::try_mutate(sender, payment_id, |maybe_payment| -> Result<_, DispatchError> {
-			if let Ok(payment) = maybe_payment {
+		/// Prunes a payment request once its expiry has passed. This is called
+		/// by the scheduler, but can be called by anyone.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::expire_request())]
+		pub fn expire_request(origin: OriginFor<T>, payment_id: T::PaymentId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let (sender, _) = PaymentParties::<T>::get(&payment_id)?;
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(
+				payment.state == PaymentState::PaymentRequested,
+				Error::<T>::InvalidAction
+			);
+			ensure!(
+				Self::is_request_expired(&payment_id),
+				Error::<T>::PaymentRequestNotExpired
+			);
+
+			Self::remove_request(&sender, payment_id)?;
+
+			Self::deposit_event(Event::PaymentRequestExpired { payment_id });
+			Ok(().into())
+		}
+
+		/// Allows the beneficiary of a payment to discount part of its amount
+		/// (e.g. honouring a coupon code). The discounted amount is returned to
+		/// the sender right away if the payment is already funded.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::grant_discount())]
+		pub fn grant_discount(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let beneficiary = T::BeneficiaryOrigin::ensure_origin(origin)?;
+			let (sender, b) = PaymentParties::<T>::get(&payment_id)?;
+			ensure!(beneficiary == b, Error::<T>::InvalidBeneficiary);
+			// Milestones already split the amount of the payment
+			ensure!(
+				!PaymentMilestones::<T>::contains_key(payment_id),
+				Error::<T>::InvalidAction
+			);
+
+			let amount = Payment::<T>::try_mutate(&sender, payment_id, |maybe_payment| -> Result<_, DispatchError> {
+				let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
+				let discounted = payment.apply_discount(Discount {
+					target: DiscountTarget::Amount,
+					amount,
+				})?;
+
+				match payment.state {
+					PaymentState::PaymentRequested => {}
+					PaymentState::Created | PaymentState::RefundRequested { .. } => {
+						let reason = &HoldReason::TransferPayment.into();
+						T::Assets::release(payment.asset.clone(), reason, &beneficiary, discounted, Exact)
+							.map_err(|_| Error::<T>::ReleaseFailed)?;
+						T::Assets::transfer(payment.asset.clone(), &beneficiary, &sender, discounted, Expendable)
+							.map_err(|_| Error::<T>::TransferFailed)?;
+					}
+					_ => fail!(Error::<T>::InvalidAction),
+				}
+				Ok(discounted)
+			})?;
+
+			Self::deposit_event(Event::PaymentDiscountGranted { payment_id, amount });
+			Ok(().into())
+		}
+
+		/// Lets the sender or the beneficiary of a payment under review submit
+		/// the hash of a piece of evidence backing their case, for whoever
+		/// resolves the dispute. A deposit is held from the submitter for the
+		/// evidence until the dispute is resolved.
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::submit_evidence())]
+		pub fn submit_evidence(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			hash: T::Hash,
+			kind: EvidenceKind,
+		) -> DispatchResultWithPostInfo {
+			let (submitted_by, sender, beneficiary) = Self::ensure_payment_party(origin, &payment_id)?;
+			let who = match submitted_by {
+				Role::Sender => sender.clone(),
+				Role::Beneficiary => beneficiary,
+			};
+
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::NeedsReview, Error::<T>::InvalidAction);
+
+			let submitted = PaymentEvidence::<T>::try_mutate(payment_id, |evidence| -> Result<_, DispatchError> {
+				evidence
+					.try_push(Evidence {
+						submitted_by: submitted_by.clone(),
+						hash,
+						kind: kind.clone(),
+						submitted_at: frame_system::Pallet::<T>::block_number(),
+					})
+					.map_err(|_| Error::<T>::TooMuchEvidence)?;
+				Ok(evidence.iter().filter(|e| e.submitted_by == submitted_by).count())
+			})?;
+			Self::update_evidence_deposit(&who, payment_id, submitted)?;
+
+			Self::deposit_event(Event::PaymentEvidenceSubmitted {
+				payment_id,
+				who,
+				hash,
+				kind,
+			});
+			Ok(().into())
+		}
+
+		/// Creates a payment like [`pay`](Pallet::pay) that is locked by a hash
+		/// (e.g. of a delivery code, or the secret of an atomic swap). The
+		/// beneficiary gets paid by revealing its preimage through
+		/// [`claim_payment`](Pallet::claim_payment) before `timeout` blocks
+		/// pass, after which the payment is refunded to the sender.
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as Config>::WeightInfo::pay_with_hash_lock(
+			remark.as_ref().map(|x| x.len() as u32).unwrap_or(0)
+		))]
+		pub fn pay_with_hash_lock(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			hash: T::Hash,
+			timeout: BlockNumberFor<T>,
+			remark: Option<BoundedDataOf<T>>,
+			arbiter: Option<T::Arbiter>,
+		) -> DispatchResultWithPostInfo {
+			let sender = T::SenderOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!timeout.is_zero(), Error::<T>::InvalidAction);
+
+			let (payment_id, payment_detail) = Self::create_payment(
+				&sender,
+				beneficiary.clone(),
+				asset.clone(),
+				amount,
+				PaymentState::Created,
+				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+			Self::reserve_payment_amount(&sender, payment_detail)?;
+			Self::set_arbiter(payment_id, &sender, &beneficiary, arbiter.clone())?;
+
+			// Refund the payment unless it's claimed before the timeout
+			let timeout = frame_system::Pallet::<T>::block_number()
+				.checked_add(&timeout)
+				.ok_or(Error::<T>::MathError)?;
+			let cancel_call = <T as Config>::RuntimeCall::from(pallet::Call::<T>::cancel { payment_id });
+			T::Scheduler::schedule_named(
+				("payment_hash_lock", payment_id).using_encoded(blake2_256),
+				DispatchTime::At(timeout),
+				None,
+				63,
+				frame_system::RawOrigin::Signed(beneficiary).into(),
+				T::Preimages::bound(cancel_call)?,
+			)?;
+			PaymentHashLocks::<T>::insert(payment_id, HashLock { hash, timeout });
+
+			Self::deposit_event(Event::PaymentCreated {
+				payment_id,
+				asset,
+				amount,
+				remark,
+				arbiter,
+			});
+			Self::deposit_event(Event::PaymentHashLocked {
+				payment_id,
+				hash,
+				timeout,
+			});
+			Ok(().into())
+		}
+
+		/// Allows the beneficiary of a hash-locked payment to get paid by
+		/// revealing the preimage of its hash before the timeout.
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_payment())]
+		pub fn claim_payment(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			preimage: BoundedDataOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let beneficiary = T::BeneficiaryOrigin::ensure_origin(origin)?;
+			let (sender, b) = PaymentParties::<T>::get(&payment_id)?;
+			ensure!(beneficiary == b, Error::<T>::InvalidBeneficiary);
+
+			let hash_lock = PaymentHashLocks::<T>::get(payment_id).ok_or(Error::<T>::InvalidAction)?;
+			ensure!(
+				hash_lock.timeout > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::HashLockExpired
+			);
+			ensure!(
+				T::Hashing::hash(&preimage) == hash_lock.hash,
+				Error::<T>::InvalidPreimage
+			);
+
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+
+			Self::settle_payment(&sender, &beneficiary, &payment_id, None)?;
+
+			Self::deposit_event(Event::PaymentClaimed { payment_id, preimage });
+			Ok(().into())
+		}
+
+		/// Lets the sender or the beneficiary of a payment propose to settle it
+		/// with a partial refund, keeping `beneficiary_share` of its amount for
+		/// the beneficiary and refunding the rest to the sender. Once the other
+		/// party accepts it, the payment is settled without going through a
+		/// dispute. A new proposal from either party replaces the previous one.
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as Config>::WeightInfo::propose_partial_refund())]
+		pub fn propose_partial_refund(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			beneficiary_share: Percent,
+		) -> DispatchResultWithPostInfo {
+			let (proposed_by, sender, _) = Self::ensure_payment_party(origin, &payment_id)?;
+
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(
+				matches!(
+					payment.state,
+					PaymentState::Created | PaymentState::RefundRequested { .. }
+				),
+				Error::<T>::InvalidAction
+			);
+
+			RefundProposals::<T>::insert(
+				payment_id,
+				RefundProposal {
+					proposed_by: proposed_by.clone(),
+					beneficiary_share,
+				},
+			);
+
+			Self::deposit_event(Event::PartialRefundProposed {
+				payment_id,
+				proposed_by,
+				beneficiary_share,
+			});
+			Ok(().into())
+		}
+
+		/// Accepts the partial refund proposed by the other party of a
+		/// payment, settling it. The share is given to make sure the accepted
+		/// proposal is the expected one.
+		#[pallet::call_index(21)]
+		#[pallet::weight(<T as Config>::WeightInfo::accept_partial_refund())]
+		pub fn accept_partial_refund(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			beneficiary_share: Percent,
+		) -> DispatchResultWithPostInfo {
+			let (accepted_by, sender, beneficiary) = Self::ensure_payment_party(origin, &payment_id)?;
+
+			let proposal = RefundProposals::<T>::get(payment_id).ok_or(Error::<T>::InvalidRefundProposal)?;
+			ensure!(proposal.proposed_by != accepted_by, Error::<T>::InvalidAction);
+			ensure!(
+				proposal.beneficiary_share == beneficiary_share,
+				Error::<T>::InvalidRefundProposal
+			);
+
+			let refund = Payment::<T>::try_mutate(&sender, &payment_id, |maybe_payment| {
+				let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
 				ensure!(
-					payment.state == PaymentState::PaymentRequested,
-					Error::<T>::PaymentAlreadyInProcess
+					matches!(
+						payment.state,
+						PaymentState::Created | PaymentState::RefundRequested { .. }
+					),
+					Error::<T>::InvalidAction
 				);
-			}
 
-			let incentive_amount = incentive_percentage.mul_floor(amount);
+				let refund = payment
+					.amount
+					.saturating_sub(beneficiary_share.mul_floor(payment.amount));
+				Self::refund_partially(&sender, payment, refund)?;
+				Ok::<_, DispatchError>(refund)
+			})?;
+			Self::settle_payment(&sender, &beneficiary, &payment_id, None)?;
+			// No longer needed to cancel or release the payment
+			let _ = T::Scheduler::cancel_named(("payment", payment_id).using_encoded(blake2_256));
+			Self::cancel_auto_release(payment_id);
+
+			Self::deposit_event(Event::PartialRefundAccepted { payment_id, refund });
+			Ok(().into())
+		}
+
+		/// Resolves a disputed payment. Payments that named an arbiter when
+		/// they were created can only be resolved by it, who gets the
+		/// incentive, and the rest by the `DisputeResolver`. If it's not
+		/// resolved within the `DisputeReviewPeriod`, the
+		/// `DefaultDisputeResult` is applied.
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T as Config>::WeightInfo::resolve_dispute())]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+			dispute_result: DisputeResult,
+		) -> DispatchResultWithPostInfo {
+			let dispute_resolver = match PaymentArbiters::<T>::get(payment_id) {
+				Some(arbiter) => T::ArbiterOrigin::ensure_origin(origin, &arbiter)?,
+				None => T::DisputeResolver::ensure_origin(origin)?,
+			};
+			let (sender, beneficiary) = PaymentParties::<T>::get(&payment_id)?;
 
-			let fees_details: Fees<T> = T::FeeHandler::apply_fees(&asset, sender, &beneficiary, &amount, remark);
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::NeedsReview, Error::<T>::InvalidAction);
 
-			let new_payment = PaymentDetail::<T> {
+			let dispute = Some((dispute_result, Some(dispute_resolver)));
+			Self::settle_payment(&sender, &beneficiary, &payment_id, dispute)?;
+			let _ = T::Scheduler::cancel_named(("payment_review", payment_id).using_encoded(blake2_256));
+
+			Self::deposit_event(Event::PaymentDisputeResolved { payment_id });
+			Ok(().into())
+		}
+
+		/// Applies the `DefaultDisputeResult` to a payment whose dispute wasn't
+		/// resolved within the `DisputeReviewPeriod`. Nobody resolved it, so
+		/// both parties get their incentive back.
+		#[pallet::call_index(22)]
+		#[pallet::weight(<T as Config>::WeightInfo::apply_default_dispute_result())]
+		pub fn apply_default_dispute_result(
+			origin: OriginFor<T>,
+			payment_id: T::PaymentId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let (sender, beneficiary) = PaymentParties::<T>::get(&payment_id)?;
+
+			let payment = Payment::<T>::get(&sender, &payment_id).map_err(|_| Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::NeedsReview, Error::<T>::InvalidAction);
+
+			let dispute = Some((T::DefaultDisputeResult::get(), None));
+			Self::settle_payment(&sender, &beneficiary, &payment_id, dispute)?;
+			let _ = T::Scheduler::cancel_named(("payment_review", payment_id).using_encoded(blake2_256));
+
+			Self::deposit_event(Event::PaymentDisputeResolved { payment_id });
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The function will create a new payment. The fee and incentive
+	/// amounts will be calculated and the `PaymentDetail` will be added to
+	/// storage.
+	#[allow(clippy::too_many_arguments)]
+	fn create_payment(
+		sender: &T::AccountId,
+		beneficiary: T::AccountId,
+		asset: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		payment_state: PaymentState<BlockNumberFor<T>>,
+		incentive_percentage: Percent,
+		remark: Option<&[u8]>,
+	) -> Result<(T::PaymentId, PaymentDetail<T>), DispatchError> {
+		let payment_id = T::PaymentId::next(sender, &beneficiary).ok_or(Error::<T>::NoPaymentIdAvailable)?;
+		// Never overwrite an existing payment (or request) with the same id
+		ensure!(
+			!PaymentParties::<T>::contains_key(payment_id),
+			Error::<T>::PaymentAlreadyInProcess
+		);
+		let payment = Payment::<T>::try_mutate(sender, payment_id, |maybe_payment| -> Result<_, DispatchError> {
+			ensure!(maybe_payment.is_err(), Error::<T>::PaymentAlreadyInProcess);
+
+			let new_payment = Self::new_payment_detail(
+				sender,
+				beneficiary.clone(),
 				asset,
 				amount,
-				beneficiary: beneficiary.clone(),
-				incentive_amount,
-				state: payment_state,
-				fees: fees_details,
-			};
+				payment_state,
+				incentive_percentage,
+				remark,
+			)?;
 			*maybe_payment = Ok(new_payment.clone());
+			PaymentsByBeneficiary::<T>::insert(&beneficiary, payment_id, ());
 			PaymentParties::<T>::insert(payment_id, (sender, beneficiary));
 
 			Ok(new_payment)
+		})?;
+
+		// The beneficiary is the one creating a payment request
+		let creator = if payment.state == PaymentState::PaymentRequested {
+			&payment.beneficiary
+		} else {
+			sender
+		};
+		Self::take_deposit(creator, payment_id, Self::deposit_footprint())?;
+
+		Ok((payment_id, payment))
+	}
+
+	/// The details of a new payment, with the fees and discounts of the fee
+	/// handler applied and the incentive calculated on the discounted amount.
+	fn new_payment_detail(
+		sender: &T::AccountId,
+		beneficiary: T::AccountId,
+		asset: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		payment_state: PaymentState<BlockNumberFor<T>>,
+		incentive_percentage: Percent,
+		remark: Option<&[u8]>,
+	) -> Result<PaymentDetail<T>, DispatchError> {
+		let fees_details: Fees<T> = T::FeeHandler::apply_fees(&asset, sender, &beneficiary, &amount, remark);
+		let discounts = T::FeeHandler::apply_discounts(&asset, sender, &beneficiary, &amount, remark);
+
+		let mut new_payment = PaymentDetail::<T> {
+			asset,
+			amount,
+			beneficiary,
+			incentive_amount: Zero::zero(),
+			state: payment_state,
+			fees: fees_details,
+			discounts: Default::default(),
+		};
+		for discount in discounts {
+			new_payment.apply_discount(discount)?;
+		}
+		new_payment.incentive_amount = incentive_percentage.mul_floor(new_payment.amount);
+		Ok(new_payment)
+	}
+
+	/// What a payment from `sender` to `beneficiary` would be charged if it
+	/// was made now, so wallets can show it before it's signed.
+	pub fn quote(
+		sender: &T::AccountId,
+		beneficiary: T::AccountId,
+		asset: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		remark: Option<&[u8]>,
+	) -> Result<FeeQuoteOf<T>, DispatchError> {
+		let payment = Self::new_payment_detail(
+			sender,
+			beneficiary,
+			asset,
+			amount,
+			PaymentState::Created,
+			T::IncentivePercentage::get(),
+			remark,
+		)?;
+		let (_, sender_fees_mandatory, sender_fees_optional) = payment.fees.summary_for(Role::Sender, false)?;
+
+		Ok(FeeQuote {
+			amount: payment.amount,
+			incentive: payment.incentive_amount,
+			sender_total: payment
+				.amount
+				.saturating_add(payment.incentive_amount)
+				.saturating_add(sender_fees_mandatory)
+				.saturating_add(sender_fees_optional),
+			sender_fees: payment.fees.sender_pays.into_inner(),
+			beneficiary_fees: payment.fees.beneficiary_pays.into_inner(),
+			discounts: payment.discounts.into_inner(),
 		})
-		.map(|payment| (payment_id, payment))
+	}
+
+	/// The details of a payment, if it's still in storage.
+	pub fn payment_detail(payment_id: T::PaymentId) -> Option<PaymentDetail<T>> {
+		let (sender, _) = PaymentParties::<T>::get(payment_id).ok()?;
+		Payment::<T>::get(sender, payment_id).ok()
+	}
+
+	/// Up to `limit` payments made by the given account, starting after the
+	/// `start` payment (i.e. the last one of the previous page).
+	pub fn payments_by_sender(
+		sender: &T::AccountId,
+		start: Option<T::PaymentId>,
+		limit: u32,
+	) -> Vec<(T::PaymentId, PaymentDetail<T>)> {
+		let payments = match start {
+			Some(payment_id) => {
+				Payment::<T>::iter_prefix_from(sender, Payment::<T>::hashed_key_for(sender, payment_id))
+			}
+			None => Payment::<T>::iter_prefix(sender),
+		};
+		payments.take(limit as usize).collect()
+	}
+
+	/// Up to `limit` payments the given account is the beneficiary of,
+	/// starting after the `start` payment (i.e. the last one of the previous
+	/// page).
+	pub fn payments_by_beneficiary(
+		beneficiary: &T::AccountId,
+		start: Option<T::PaymentId>,
+		limit: u32,
+	) -> Vec<(T::PaymentId, PaymentDetail<T>)> {
+		let payment_ids = match start {
+			Some(payment_id) => PaymentsByBeneficiary::<T>::iter_key_prefix_from(
+				beneficiary,
+				PaymentsByBeneficiary::<T>::hashed_key_for(beneficiary, payment_id),
+			),
+			None => PaymentsByBeneficiary::<T>::iter_key_prefix(beneficiary),
+		};
+		payment_ids
+			.filter_map(|payment_id| Some((payment_id, Self::payment_detail(payment_id)?)))
+			.take(limit as usize)
+			.collect()
+	}
+
+	/// Schedules the release of a payment after the given delay, on behalf of
+	/// its creator.
+	fn schedule_auto_release(
+		sender: &T::AccountId,
+		payment_id: T::PaymentId,
+		delay: BlockNumberFor<T>,
+	) -> Result<BlockNumberFor<T>, DispatchError> {
+		let release_at = frame_system::Pallet::<T>::block_number()
+			.checked_add(&delay)
+			.ok_or(Error::<T>::MathError)?;
+		let release_call = <T as Config>::RuntimeCall::from(pallet::Call::<T>::release { payment_id });
+
+		T::Scheduler::schedule_named(
+			("payment_release", payment_id).using_encoded(blake2_256),
+			DispatchTime::At(release_at),
+			None,
+			63,
+			frame_system::RawOrigin::Signed(sender.clone()).into(),
+			T::Preimages::bound(release_call)?,
+		)?;
+
+		Ok(release_at)
+	}
+
+	/// Cancels the automatic release of a payment, if it was scheduled.
+	fn cancel_auto_release(payment_id: T::PaymentId) {
+		let _ = T::Scheduler::cancel_named(("payment_release", payment_id).using_encoded(blake2_256));
+	}
+
+	/// Creates the payment of a period of the subscription, which is held in
+	/// escrow like any other payment and released automatically at the end of
+	/// the period.
+	fn escrow_subscription_period(subscription: &SubscriptionOf<T>) -> Result<T::PaymentId, DispatchError> {
+		let Subscription {
+			sender,
+			beneficiary,
+			asset,
+			amount,
+			period,
+			..
+		} = subscription.clone();
+
+		let (payment_id, payment) = Self::create_payment(
+			&sender,
+			beneficiary,
+			asset,
+			amount,
+			PaymentState::Created,
+			T::IncentivePercentage::get(),
+			None,
+		)?;
+		Self::reserve_payment_amount(&sender, payment)?;
+		let release_at = Self::schedule_auto_release(&sender, payment_id, period)?;
+		Self::deposit_event(Event::PaymentAutoReleaseScheduled { payment_id, release_at });
+
+		Ok(payment_id)
+	}
+
+	fn is_request_expired(payment_id: &T::PaymentId) -> bool {
+		PaymentRequestExpiry::<T>::get(payment_id)
+			.is_some_and(|expiry| expiry <= frame_system::Pallet::<T>::block_number())
+	}
+
+	fn clear_request_expiry(payment_id: T::PaymentId) {
+		PaymentRequestExpiry::<T>::remove(payment_id);
+		let _ = T::Scheduler::cancel_named(("payment_request", payment_id).using_encoded(blake2_256));
+	}
+
+	/// Removes an outstanding payment request, which holds no funds.
+	fn remove_request(sender: &T::AccountId, payment_id: T::PaymentId) -> DispatchResult {
+		Self::clear_request_expiry(payment_id);
+		Self::remove_payment(sender, payment_id)
+	}
+
+	/// Returns a number that is unique to every payment created in the
+	/// current block, or `None` once no more payments fit in the block.
+	pub fn next_payment_index() -> Option<u16> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let index = match PaymentsInBlock::<T>::get() {
+			Some((block, count)) if block == now => count,
+			_ => 0,
+		};
+		PaymentsInBlock::<T>::put((now, index.checked_add(1)?));
+		Some(index)
+	}
+
+	/// Names the arbiter of a payment, which can't be one of its parties.
+	fn set_arbiter(
+		payment_id: T::PaymentId,
+		sender: &T::AccountId,
+		beneficiary: &T::AccountId,
+		maybe_arbiter: Option<T::Arbiter>,
+	) -> DispatchResult {
+		if let Some(arbiter) = maybe_arbiter {
+			let account = T::ArbiterAccount::convert(arbiter.clone());
+			ensure!(
+				account != *sender && account != *beneficiary,
+				Error::<T>::InvalidArbiter
+			);
+			PaymentArbiters::<T>::insert(payment_id, arbiter);
+		}
+		Ok(())
+	}
+
+	/// The storage used by a live payment, which its creator pays a deposit
+	/// for.
+	pub(crate) fn deposit_footprint() -> Footprint {
+		Footprint::from_mel::<(PaymentDetail<T>, (T::AccountId, T::AccountId), T::Arbiter)>()
+	}
+
+	/// Holds a deposit from the creator of a payment (or subscription) for the
+	/// storage it uses.
+	fn take_deposit(who: &T::AccountId, payment_id: T::PaymentId, footprint: Footprint) -> DispatchResult {
+		let ticket = T::Consideration::new(who, footprint)?;
+		PaymentDeposits::<T>::insert(payment_id, (who.clone(), ticket));
+		Ok(())
+	}
+
+	/// Releases the deposit of a payment that is no longer live.
+	fn release_deposit(payment_id: T::PaymentId) -> DispatchResult {
+		if let Some((who, ticket)) = PaymentDeposits::<T>::take(payment_id) {
+			ticket.drop(&who)?;
+		}
+		Ok(())
+	}
+
+	/// Releases the deposit of a finished payment, leaving it in storage until
+	/// its retention period is over.
+	fn finish_payment(payment_id: T::PaymentId) -> DispatchResult {
+		let prune_at = frame_system::Pallet::<T>::block_number().saturating_add(T::FinishedPaymentRetention::get());
+		FinishedPayments::<T>::insert(prune_at, payment_id, ());
+		FinishedPaymentsCursor::<T>::mutate(|cursor| {
+			*cursor = Some(cursor.map_or(prune_at, |cursor| cursor.min(prune_at)));
+		});
+		Self::remove_hash_lock(payment_id);
+		RefundProposals::<T>::remove(payment_id);
+		Self::clear_evidence(payment_id)?;
+		Self::release_deposit(payment_id)
+	}
+
+	/// Ensures the origin is the sender or the beneficiary of a payment,
+	/// returning its role along with the sender and the beneficiary.
+	fn ensure_payment_party(
+		origin: T::RuntimeOrigin,
+		payment_id: &T::PaymentId,
+	) -> Result<(Role, T::AccountId, T::AccountId), DispatchError> {
+		let who = T::SenderOrigin::try_origin(origin).or_else(T::BeneficiaryOrigin::ensure_origin)?;
+		let (sender, beneficiary) = PaymentParties::<T>::get(payment_id)?;
+		let role = if who == sender {
+			Role::Sender
+		} else if who == beneficiary {
+			Role::Beneficiary
+		} else {
+			fail!(Error::<T>::InvalidAction)
+		};
+		Ok((role, sender, beneficiary))
+	}
+
+	/// Removes the hash lock of a payment (if any) along with its scheduled
+	/// refund.
+	fn remove_hash_lock(payment_id: T::PaymentId) {
+		if PaymentHashLocks::<T>::take(payment_id).is_some() {
+			let _ = T::Scheduler::cancel_named(("payment_hash_lock", payment_id).using_encoded(blake2_256));
+		}
+	}
+
+	/// Holds (or updates) the deposit of a party of a payment for the given
+	/// amount of evidence it submitted.
+	fn update_evidence_deposit(who: &T::AccountId, payment_id: T::PaymentId, submitted: usize) -> DispatchResult {
+		let footprint = Footprint::from_parts(submitted, submitted.saturating_mul(EvidenceOf::<T>::max_encoded_len()));
+		let ticket = match EvidenceDeposits::<T>::take(payment_id, who) {
+			Some(ticket) => ticket.update(who, footprint)?,
+			None => T::Consideration::new(who, footprint)?,
+		};
+		EvidenceDeposits::<T>::insert(payment_id, who, ticket);
+		Ok(())
+	}
+
+	/// Removes the evidence submitted for a payment, releasing the deposits
+	/// of its submitters.
+	fn clear_evidence(payment_id: T::PaymentId) -> DispatchResult {
+		PaymentEvidence::<T>::remove(payment_id);
+		for (who, ticket) in EvidenceDeposits::<T>::drain_prefix(payment_id) {
+			ticket.drop(&who)?;
+		}
+		Ok(())
+	}
+
+	/// Removes a payment from storage, releasing its deposit.
+	fn remove_payment(sender: &T::AccountId, payment_id: T::PaymentId) -> DispatchResult {
+		Payment::<T>::remove(sender, payment_id);
+		if let Ok((_, beneficiary)) = PaymentParties::<T>::take(payment_id) {
+			PaymentsByBeneficiary::<T>::remove(beneficiary, payment_id);
+		}
+		PaymentMilestones::<T>::remove(payment_id);
+		PaymentSplits::<T>::remove(payment_id);
+		PaymentArbiters::<T>::remove(payment_id);
+		Self::remove_hash_lock(payment_id);
+		RefundProposals::<T>::remove(payment_id);
+		Self::release_deposit(payment_id)
+	}
+
+	pub(crate) fn prune_finished_payments(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let weight_per_payment = T::WeightInfo::prune_finished_payment();
+		let weight_per_block = T::DbWeight::get().reads(1);
+		let mut consumed = T::DbWeight::get().reads_writes(1, 1);
+		if consumed.any_gt(limit) {
+			return Weight::zero();
+		}
+
+		let Some(cursor) = FinishedPaymentsCursor::<T>::get() else {
+			return T::DbWeight::get().reads(1);
+		};
+
+		// Only the blocks whose retention is over are visited, so payments that
+		// are not due yet are never read
+		let mut block = cursor;
+		while block <= now {
+			if consumed.saturating_add(weight_per_block).any_gt(limit) {
+				break;
+			}
+			consumed.saturating_accrue(weight_per_block);
+
+			let mut payments = FinishedPayments::<T>::iter_key_prefix(block);
+			let mut batch = Vec::new();
+			let mut exhausted = false;
+			loop {
+				if consumed.saturating_add(weight_per_payment).any_gt(limit) {
+					break;
+				}
+				let Some(payment_id) = payments.next() else {
+					exhausted = true;
+					break;
+				};
+				consumed.saturating_accrue(weight_per_payment);
+				batch.push(payment_id);
+			}
+			drop(payments);
+
+			for payment_id in batch {
+				if let Ok((sender, beneficiary)) = PaymentParties::<T>::get(payment_id) {
+					Payment::<T>::remove(&sender, payment_id);
+					PaymentParties::<T>::remove(payment_id);
+					PaymentsByBeneficiary::<T>::remove(&beneficiary, payment_id);
+					PaymentArbiters::<T>::remove(payment_id);
+					Self::deposit_event(Event::PaymentPruned { payment_id });
+				}
+				FinishedPayments::<T>::remove(block, payment_id);
+			}
+
+			if !exhausted {
+				break;
+			}
+			block.saturating_inc();
+		}
+
+		if block != cursor {
+			FinishedPaymentsCursor::<T>::put(block);
+		}
+		consumed
 	}
 
 	fn reserve_payment_amount(sender: &T::AccountId, payment: PaymentDetail<T>) -> DispatchResult {
@@ -598,11 +1963,13 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Settles a payment, paying the beneficiary all of its amount or
+	/// applying the result of a dispute.
 	fn settle_payment(
 		sender: &T::AccountId,
 		beneficiary: &T::AccountId,
 		payment_id: &T::PaymentId,
-		maybe_dispute: Option<(DisputeResult, T::AccountId)>,
+		maybe_dispute: Option<(DisputeResult, Option<T::AccountId>)>,
 	) -> DispatchResult {
 		Payment::<T>::try_mutate(sender, payment_id, |maybe_payment| -> DispatchResult {
 			let payment = maybe_payment.as_mut().map_err(|_| Error::<T>::InvalidPayment)?;
@@ -618,14 +1985,14 @@ impl<T: Config> Pallet<T> {
 				.saturating_add(payment.incentive_amount)
 				.saturating_add(total_sender_fee_amount_optional);
 
-			T::Assets::release(payment.asset.clone(), reason, sender, total_sender_release, Exact)
-				.map_err(|_| Error::<T>::ReleaseFailed)?;
+			// Nothing is left on hold once a payment is fully refunded
+			if !total_sender_release.is_zero() {
+				T::Assets::release(payment.asset.clone(), reason, sender, total_sender_release, Exact)
+					.map_err(|_| Error::<T>::ReleaseFailed)?;
+			}
 
-			let (
-				fee_beneficiary_recipients,
-				_total_beneficiary_fee_amount_mandatory,
-				_total_beneficiary_fee_amount_optional,
-			) = payment.fees.summary_for(Role::Beneficiary, is_dispute)?;
+			let (fee_beneficiary_recipients, total_beneficiary_fee_amount, total_beneficiary_fee_amount_optional) =
+				payment.fees.summary_for(Role::Beneficiary, is_dispute)?;
 
 			let mut beneficiary_release_amount = payment.amount;
 
@@ -633,19 +2000,25 @@ impl<T: Config> Pallet<T> {
 				beneficiary_release_amount = beneficiary_release_amount.saturating_add(payment.incentive_amount);
 			}
 
-			T::Assets::release(
-				payment.asset.clone(),
-				reason,
-				beneficiary,
-				beneficiary_release_amount,
-				Exact,
-			)
-			.map_err(|_| Error::<T>::ReleaseFailed)?;
+			if !beneficiary_release_amount.is_zero() {
+				T::Assets::release(
+					payment.asset.clone(),
+					reason,
+					beneficiary,
+					beneficiary_release_amount,
+					Exact,
+				)
+				.map_err(|_| Error::<T>::ReleaseFailed)?;
+			}
 
 			Self::try_transfer_fees(sender, payment, fee_sender_recipients, is_dispute)?;
 
 			Self::try_transfer_fees(beneficiary, payment, fee_beneficiary_recipients, is_dispute)?;
 
+			// The part of the amount the beneficiary keeps, which is split with the
+			// co-beneficiaries of the payment
+			let mut beneficiary_amount = payment.amount;
+
 			if let Some((dispute_result, resolver)) = maybe_dispute {
 				match dispute_result.in_favor_of {
 					Role::Sender => {
@@ -653,40 +2026,170 @@ impl<T: Config> Pallet<T> {
 
 						// Beneficiary looses the dispute and has to transfer the incentive_amount to
 						// the dispute_resolver.
-						T::Assets::transfer(
-							payment.asset.clone(),
-							beneficiary,
-							&resolver,
-							payment.incentive_amount,
-							Expendable,
-						)
-						.map_err(|_| Error::<T>::TransferFailed)?;
+						if let Some(resolver) = &resolver {
+							T::Assets::transfer(
+								payment.asset.clone(),
+								beneficiary,
+								resolver,
+								payment.incentive_amount,
+								Expendable,
+							)
+							.map_err(|_| Error::<T>::TransferFailed)?;
+						}
 
 						T::Assets::transfer(payment.asset.clone(), beneficiary, sender, amount_to_sender, Expendable)
 							.map_err(|_| Error::<T>::TransferFailed)?;
+						beneficiary_amount = payment.amount.saturating_sub(amount_to_sender);
 					}
 					Role::Beneficiary => {
 						let amount_to_beneficiary = dispute_result.percent_beneficiary.mul_floor(payment.amount);
 						let amount_to_sender = payment.amount.saturating_sub(amount_to_beneficiary);
 
-						T::Assets::transfer(
-							payment.asset.clone(),
-							sender,
-							&resolver,
-							payment.incentive_amount,
-							Expendable,
-						)
-						.map_err(|_| Error::<T>::TransferFailed)?;
+						if let Some(resolver) = &resolver {
+							T::Assets::transfer(
+								payment.asset.clone(),
+								sender,
+								resolver,
+								payment.incentive_amount,
+								Expendable,
+							)
+							.map_err(|_| Error::<T>::TransferFailed)?;
+						}
 
 						T::Assets::transfer(payment.asset.clone(), beneficiary, sender, amount_to_sender, Expendable)
 							.map_err(|_| Error::<T>::TransferFailed)?;
+						beneficiary_amount = amount_to_beneficiary;
 					}
 				}
 			}
 
+			// Shares are taken from what the beneficiary keeps after every fee it is
+			// charged, so waiving the optional ones on disputes only benefits it
+			if let Some(splits) = PaymentSplits::<T>::take(payment_id) {
+				let net_amount = beneficiary_amount
+					.saturating_sub(total_beneficiary_fee_amount)
+					.saturating_sub(total_beneficiary_fee_amount_optional);
+				Self::settle_splits(payment_id, beneficiary, &payment.asset, net_amount, splits)?;
+			}
+
 			payment.state = PaymentState::Finished;
+			PaymentMilestones::<T>::remove(payment_id);
 			Ok(())
-		})
+		})?;
+
+		Self::finish_payment(*payment_id)
+	}
+
+	/// Pays every co-beneficiary of a payment its share of the amount the
+	/// beneficiary got.
+	fn settle_splits(
+		payment_id: &T::PaymentId,
+		beneficiary: &T::AccountId,
+		asset: &AssetIdOf<T>,
+		amount: BalanceOf<T>,
+		splits: SplitsOf<T>,
+	) -> DispatchResult {
+		for (co_beneficiary, share) in splits {
+			let share_amount = share.mul_floor(amount);
+			if share_amount.is_zero() {
+				continue;
+			}
+			T::Assets::transfer(asset.clone(), beneficiary, &co_beneficiary, share_amount, Expendable)
+				.map_err(|_| Error::<T>::TransferFailed)?;
+			Self::deposit_event(Event::PaymentSplitSettled {
+				payment_id: *payment_id,
+				beneficiary: co_beneficiary,
+				amount: share_amount,
+			});
+		}
+		Ok(())
+	}
+
+	/// Settles the given amount of a payment, along with the proportional
+	/// part of its fees and incentive, leaving the rest of the payment
+	/// reserved. Co-beneficiaries are paid their share of the settled amount
+	/// right away, as they are on the rest once it is settled.
+	fn settle_partially(
+		sender: &T::AccountId,
+		payment_id: &T::PaymentId,
+		payment: &mut PaymentDetail<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let portion = Perbill::from_rational(amount, payment.amount);
+		let incentive_amount = portion.mul_floor(payment.incentive_amount);
+		let fees = payment.fees.split_off(portion);
+		let beneficiary = payment.beneficiary.clone();
+
+		let (fee_sender_recipients, total_sender_fee_amount_mandatory, total_sender_fee_amount_optional) =
+			fees.summary_for(Role::Sender, false)?;
+		let (fee_beneficiary_recipients, total_beneficiary_fee_amount, total_beneficiary_fee_amount_optional) =
+			fees.summary_for(Role::Beneficiary, false)?;
+
+		let total_sender_release = total_sender_fee_amount_mandatory
+			.saturating_add(incentive_amount)
+			.saturating_add(total_sender_fee_amount_optional);
+
+		let reason = &HoldReason::TransferPayment.into();
+		for (who, release_amount) in [(sender, total_sender_release), (&beneficiary, amount)] {
+			if !release_amount.is_zero() {
+				T::Assets::release(payment.asset.clone(), reason, who, release_amount, Exact)
+					.map_err(|_| Error::<T>::ReleaseFailed)?;
+			}
+		}
+
+		Self::try_transfer_fees(sender, payment, fee_sender_recipients, false)?;
+		Self::try_transfer_fees(&beneficiary, payment, fee_beneficiary_recipients, false)?;
+
+		if let Some(splits) = PaymentSplits::<T>::get(payment_id) {
+			let net_amount = amount
+				.saturating_sub(total_beneficiary_fee_amount)
+				.saturating_sub(total_beneficiary_fee_amount_optional);
+			Self::settle_splits(payment_id, &beneficiary, &payment.asset, net_amount, splits)?;
+		}
+
+		payment.amount.saturating_reduce(amount);
+		payment.incentive_amount.saturating_reduce(incentive_amount);
+		Ok(())
+	}
+
+	/// Refunds the given amount of a payment straight from the hold to the
+	/// sender, along with the proportional part of the fees and incentive
+	/// held from it, so fees are only charged on the part the beneficiary
+	/// keeps.
+	fn refund_partially(sender: &T::AccountId, payment: &mut PaymentDetail<T>, refund: BalanceOf<T>) -> DispatchResult {
+		if refund.is_zero() {
+			return Ok(());
+		}
+		let portion = Perbill::from_rational(refund, payment.amount);
+		let incentive_amount = portion.mul_floor(payment.incentive_amount);
+		let fees = payment.fees.split_off(portion);
+
+		let (_, total_sender_fee_amount_mandatory, total_sender_fee_amount_optional) =
+			fees.summary_for(Role::Sender, false)?;
+		let total_sender_release = total_sender_fee_amount_mandatory
+			.saturating_add(incentive_amount)
+			.saturating_add(total_sender_fee_amount_optional);
+
+		let reason = &HoldReason::TransferPayment.into();
+		if !total_sender_release.is_zero() {
+			T::Assets::release(payment.asset.clone(), reason, sender, total_sender_release, Exact)
+				.map_err(|_| Error::<T>::ReleaseFailed)?;
+		}
+		T::Assets::transfer_on_hold(
+			payment.asset.clone(),
+			reason,
+			&payment.beneficiary,
+			sender,
+			refund,
+			Exact,
+			Free,
+			Polite,
+		)
+		.map_err(|_| Error::<T>::TransferFailed)?;
+
+		payment.amount.saturating_reduce(refund);
+		payment.incentive_amount.saturating_reduce(incentive_amount);
+		Ok(())
 	}
 
 	fn try_transfer_fees(
@@ -696,6 +2199,9 @@ impl<T: Config> Pallet<T> {
 		is_dispute: bool,
 	) -> Result<(), sp_runtime::DispatchError> {
 		for (recipient_account, fee_amount, mandatory) in fee_recipients.iter() {
+			if fee_amount.is_zero() {
+				continue;
+			}
 			if !is_dispute || *mandatory {
 				T::Assets::transfer(payment.asset.clone(), account, recipient_account, *fee_amount, Preserve)
 					.map_err(|_| Error::<T>::TransferFailed)?;
@@ -0,0 +1,71 @@
+//! Storage migrations for the payments pallet.
+
+use super::*;
+
+pub mod v1 {
+	use super::*;
+	use core::marker::PhantomData;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// The details of a payment before the discounts applied to it were
+	/// recorded along with it.
+	#[derive(Decode)]
+	pub struct OldPaymentDetail<T: Config> {
+		pub asset: AssetIdOf<T>,
+		pub amount: BalanceOf<T>,
+		pub beneficiary: AccountIdOf<T>,
+		pub incentive_amount: BalanceOf<T>,
+		pub state: PaymentState<BlockNumberFor<T>>,
+		pub fees: Fees<T>,
+	}
+
+	/// Records an empty list of discounts along with every payment.
+	pub struct AddDiscountsInner<T>(PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for AddDiscountsInner<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			Payment::<T>::translate::<OldPaymentDetail<T>, _>(|_, _, old| {
+				translated.saturating_inc();
+				Some(PaymentDetail {
+					asset: old.asset,
+					amount: old.amount,
+					beneficiary: old.beneficiary,
+					incentive_amount: old.incentive_amount,
+					state: old.state,
+					fees: old.fees,
+					discounts: Default::default(),
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+	}
+
+	/// Adds the discounts to the details of every payment, moving the pallet
+	/// to version 1.
+	pub type AddDiscounts<T> =
+		VersionedMigration<0, 1, AddDiscountsInner<T>, Pallet<T>, <T as frame_system::Config>::DbWeight>;
+}
+
+pub mod v2 {
+	use super::*;
+	use core::marker::PhantomData;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// Indexes every payment by its beneficiary.
+	pub struct IndexByBeneficiaryInner<T>(PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for IndexByBeneficiaryInner<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut indexed = 0u64;
+			for (payment_id, (_, beneficiary)) in PaymentParties::<T>::iter() {
+				PaymentsByBeneficiary::<T>::insert(&beneficiary, payment_id, ());
+				indexed.saturating_inc();
+			}
+			T::DbWeight::get().reads_writes(indexed, indexed)
+		}
+	}
+
+	/// Backfills the index of payments by beneficiary, moving the pallet to
+	/// version 2.
+	pub type IndexByBeneficiary<T> =
+		VersionedMigration<1, 2, IndexByBeneficiaryInner<T>, Pallet<T>, <T as frame_system::Config>::DbWeight>;
+}
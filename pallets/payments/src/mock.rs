@@ -1,7 +1,10 @@
 pub use crate::{self as pallet_payments, types::*, Config};
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, EqualPrivilegeOnly, OnFinalize, OnInitialize},
+	traits::{
+		fungible::HoldConsideration, AsEnsureOriginWithArg, ConstU32, ConstU64, EnsureOrigin, EnsureOriginWithArg,
+		EqualPrivilegeOnly, LinearStoragePrice, OnFinalize, OnInitialize,
+	},
 	weights::Weight,
 	PalletId,
 };
@@ -24,6 +27,7 @@ pub const SENDER_ACCOUNT: AccountId = 10;
 pub const PAYMENT_BENEFICIARY: AccountId = 11;
 pub const ASSET_ADMIN_ACCOUNT: AccountId = 3;
 pub const ROOT_ACCOUNT: AccountId = 1;
+pub const ARBITER_ACCOUNT: AccountId = 20;
 
 pub const ASSET_ID: u32 = 1;
 pub const INCENTIVE_PERCENTAGE: u8 = 10;
@@ -41,8 +45,11 @@ pub const EXPECTED_SYSTEM_SENDER_FEE: u64 = 3; // 15% of 20
 
 pub const FEE_SENDER_AMOUNT: Balance = 2;
 pub const FEE_BENEFICIARY_AMOUNT: Balance = 3;
+pub const LOYALTY_REMARK: &[u8] = b"loyalty";
+pub const LOYALTY_DISCOUNT: Balance = 4;
 pub const PAYMENT_AMOUNT: u64 = 20;
 pub const INCENTIVE_AMOUNT: u64 = PAYMENT_AMOUNT / INCENTIVE_PERCENTAGE as u64;
+pub const PAYMENT_DEPOSIT: Balance = 2;
 
 // Configure a mock runtime to test the pallet.
 #[frame_support::runtime]
@@ -128,6 +135,22 @@ impl pallet_scheduler::Config for Test {
 	type Preimages = Preimage;
 }
 
+pub struct EnsureArbiter;
+
+impl EnsureOriginWithArg<RuntimeOrigin, AccountId> for EnsureArbiter {
+	type Success = AccountId;
+
+	fn try_origin(o: RuntimeOrigin, arbiter: &AccountId) -> Result<Self::Success, RuntimeOrigin> {
+		let who = EnsureSigned::try_origin(o.clone())?;
+		(who == *arbiter).then_some(who).ok_or(o)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(arbiter: &AccountId) -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(*arbiter))
+	}
+}
+
 pub struct MockFeeHandler;
 
 const MANDATORY_FEE: bool = true;
@@ -180,6 +203,30 @@ impl crate::types::FeeHandler<Test> for MockFeeHandler {
 			beneficiary_pays: compute_fee(&beneficiary_fees),
 		}
 	}
+
+	fn apply_discounts(
+		_asset: &AssetIdOf<Test>,
+		_sender: &AccountId,
+		_beneficiary: &AccountId,
+		_amount: &Balance,
+		remark: Option<&[u8]>,
+	) -> DiscountsOf<Test> {
+		if remark != Some(LOYALTY_REMARK) {
+			return Default::default();
+		}
+
+		// Loyal senders don't pay the sender fee, and get a discount on the amount
+		BoundedVec::truncate_from(vec![
+			Discount {
+				target: DiscountTarget::Fee(Role::Sender, FEE_SENDER_ACCOUNT),
+				amount: FEE_SENDER_AMOUNT,
+			},
+			Discount {
+				target: DiscountTarget::Amount,
+				amount: LOYALTY_DISCOUNT,
+			},
+		])
+	}
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -201,6 +248,11 @@ parameter_types! {
 	pub const MaxRemarkLength: u8 = 50;
 	pub const IncentivePercentage: Percent = Percent::from_percent(INCENTIVE_PERCENTAGE);
 	pub const PaymentPalletId: PalletId = PalletId(*b"payments");
+	pub const PaymentDepositHoldReason: RuntimeHoldReason = RuntimeHoldReason::Payments(pallet_payments::HoldReason::PaymentDeposit);
+	pub const DefaultDisputeResult: DisputeResult = DisputeResult {
+		percent_beneficiary: Percent::from_percent(50),
+		in_favor_of: Role::Sender,
+	};
 }
 
 impl Config for Test {
@@ -214,14 +266,30 @@ impl Config for Test {
 	type SenderOrigin = EnsureSigned<AccountId>;
 	type BeneficiaryOrigin = EnsureSigned<AccountId>;
 	type DisputeResolver = frame_system::EnsureRootWithSuccess<u64, ConstU64<ROOT_ACCOUNT>>;
+	type DefaultDisputeResult = DefaultDisputeResult;
+	type Arbiter = AccountId;
+	type ArbiterOrigin = EnsureArbiter;
+	type ArbiterAccount = sp_runtime::traits::Identity;
 	type PalletId = PaymentPalletId;
 	type RuntimeHoldReason = RuntimeHoldReason;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		PaymentDepositHoldReason,
+		LinearStoragePrice<ConstU64<PAYMENT_DEPOSIT>, ConstU64<0>, Balance>,
+	>;
 	type MaxDiscounts = ConstU32<50>;
+	type MaxMilestones = ConstU32<20>;
+	type MaxSplits = ConstU32<5>;
+	type MaxEvidence = ConstU32<4>;
+	type PaymentRequestLifetime = ConstU64<100>;
 	type MaxFees = ConstU32<50>;
 	type RuntimeCall = RuntimeCall;
 	type Scheduler = Scheduler;
 	type Preimages = ();
 	type CancelBufferBlockLength = ConstU64<10>;
+	type DisputeReviewPeriod = ConstU64<20>;
+	type FinishedPaymentRetention = ConstU64<10>;
 	type PalletsOrigin = OriginCaller;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
@@ -235,6 +303,7 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![
 			// id, owner, is_sufficient, min_balance
+			(SENDER_ACCOUNT, INITIAL_BALANCE),
 			(FEE_SENDER_ACCOUNT, INITIAL_BALANCE),
 			(FEE_BENEFICIARY_ACCOUNT, INITIAL_BALANCE),
 			(FEE_SYSTEM_ACCOUNT, INITIAL_BALANCE),
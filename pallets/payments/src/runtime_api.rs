@@ -0,0 +1,47 @@
+//! Runtime API of the payments pallet, for wallets to quote and list
+//! payments.
+
+use crate::types::FeeQuote;
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PaymentsApi<AccountId, AssetId, Balance, PaymentId, PaymentDetail>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		PaymentId: Codec,
+		PaymentDetail: Codec,
+	{
+		/// What a payment from `sender` to `beneficiary` would be charged
+		/// (fees and incentive), or `None` if it couldn't be made.
+		fn quote(
+			sender: AccountId,
+			beneficiary: AccountId,
+			asset: AssetId,
+			amount: Balance,
+			remark: Option<Vec<u8>>,
+		) -> Option<FeeQuote<AccountId, Balance>>;
+
+		/// Up to `limit` payments made by the given account, starting after
+		/// the `start` payment (the last one of the previous page)
+		fn payments_by_sender(
+			sender: AccountId,
+			start: Option<PaymentId>,
+			limit: u32,
+		) -> Vec<(PaymentId, PaymentDetail)>;
+
+		/// Up to `limit` payments the given account is the beneficiary of,
+		/// starting after the `start` payment (the last one of the previous
+		/// page)
+		fn payments_by_beneficiary(
+			beneficiary: AccountId,
+			start: Option<PaymentId>,
+			limit: u32,
+		) -> Vec<(PaymentId, PaymentDetail)>;
+
+		/// The details of a payment
+		fn payment(payment_id: PaymentId) -> Option<PaymentDetail>;
+	}
+}
@@ -2,11 +2,17 @@ use super::*;
 use crate::{
 	mock::*,
 	types::{PaymentDetail, PaymentState},
-	Payment as PaymentStore, PaymentId,
+	FinishedPayments, Payment as PaymentStore, PaymentEvidence, PaymentHashLocks, PaymentId, PaymentMilestones,
+	PaymentParties, PaymentRequestExpiry, PaymentSplits, PaymentsByBeneficiary, RefundProposals, Subscriptions,
+};
+use frame_support::{
+	assert_err, assert_ok,
+	traits::{fungible, fungibles, OnIdle},
+	weights::{constants::WEIGHT_REF_TIME_PER_NANOS, Weight},
 };
-use frame_support::{assert_err, assert_ok, traits::fungibles, weights::constants::WEIGHT_REF_TIME_PER_NANOS};
 use weights::SubstrateWeight;
 
+use sp_core::H256;
 use sp_runtime::{BoundedVec, Perbill};
 
 const ASSERT_PAYMENT_CREATION: bool = true;
@@ -21,6 +27,8 @@ fn build_payment(assert_payment_creation: bool) -> Fees<Test> {
 		ASSET_ID,
 		PAYMENT_AMOUNT,
 		Some(remark.clone()),
+		None,
+		None,
 	));
 
 	let fees_details: Fees<Test> = <Test as pallet_payments::Config>::FeeHandler::apply_fees(
@@ -37,6 +45,7 @@ fn build_payment(assert_payment_creation: bool) -> Fees<Test> {
 			asset: ASSET_ID,
 			amount: PAYMENT_AMOUNT,
 			remark: Some(remark.clone()),
+			arbiter: None,
 		}));
 
 		assert_eq!(
@@ -47,7 +56,8 @@ fn build_payment(assert_payment_creation: bool) -> Fees<Test> {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::Created,
 				fees: fees_details.clone(),
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -125,7 +135,8 @@ fn test_pay_and_release_works() {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::Finished,
 				fees,
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -214,7 +225,8 @@ fn payment_refunded_request() {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::RefundRequested { cancel_block: 11 },
 				fees,
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -308,7 +320,8 @@ fn payment_disputed_beneficiary_wins() {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::NeedsReview,
 				fees,
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -425,7 +438,8 @@ fn payment_disputed_sender_wins() {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::NeedsReview,
 				fees,
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -477,11 +491,16 @@ fn request_payment() {
 			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
 			SENDER_ACCOUNT,
 			ASSET_ID,
-			PAYMENT_AMOUNT
+			PAYMENT_AMOUNT,
+			None,
+			None,
+			None,
 		));
 
 		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRequestCreated {
 			payment_id: mock::PaymentId(1),
+			remark: None,
+			expiry: 101,
 		}));
 
 		let fees: Fees<Test> = <Test as pallet_payments::Config>::FeeHandler::apply_fees(
@@ -500,7 +519,8 @@ fn request_payment() {
 				incentive_amount: INCENTIVE_AMOUNT,
 				state: PaymentState::PaymentRequested,
 				fees,
-				beneficiary: PAYMENT_BENEFICIARY
+				beneficiary: PAYMENT_BENEFICIARY,
+				discounts: Default::default(),
 			}
 		);
 
@@ -534,17 +554,1862 @@ fn request_payment() {
 	})
 }
 
+fn request_invoice(expires_after: Option<u64>) -> BoundedDataOf<Test> {
+	let invoice: BoundedDataOf<Test> = BoundedVec::truncate_from(H256::repeat_byte(1).as_bytes().to_vec());
+	assert_ok!(Payments::request_payment(
+		RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+		SENDER_ACCOUNT,
+		ASSET_ID,
+		PAYMENT_AMOUNT,
+		Some(invoice.clone()),
+		expires_after,
+		None,
+	));
+	invoice
+}
+
 #[test]
-fn next_id_works() {
+fn sender_can_reject_payment_request() {
+	new_test_ext().execute_with(|| {
+		let invoice = request_invoice(Some(10));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRequestCreated {
+			payment_id: PAYMENT_ID,
+			remark: Some(invoice),
+			expiry: 11,
+		}));
+
+		assert_err!(
+			Payments::reject_request(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID),
+			Error::<Test>::InvalidPayment
+		);
+		assert_ok!(Payments::reject_request(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRequestRejected {
+			payment_id: PAYMENT_ID,
+		}));
+
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_err());
+		assert!(PaymentRequestExpiry::<Test>::get(PAYMENT_ID).is_none());
+		assert_err!(
+			Payments::accept_and_pay(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			Error::<Test>::NonExistentStorageValue
+		);
+	})
+}
+
+#[test]
+fn beneficiary_can_cancel_payment_request() {
 	new_test_ext().execute_with(|| {
+		request_invoice(None);
+
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID));
+		System::assert_has_event(RuntimeEvent::Payments(
+			pallet_payments::Event::PaymentRequestCancelled { payment_id: PAYMENT_ID },
+		));
+
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_err());
 		assert_eq!(
-			mock::PaymentId::next(&SENDER_ACCOUNT, &PAYMENT_BENEFICIARY),
-			Some(mock::PaymentId(1))
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE
+		);
+	})
+}
+
+#[test]
+fn payment_request_is_pruned_after_expiry() {
+	new_test_ext().execute_with(|| {
+		request_invoice(Some(5));
+
+		assert_err!(
+			Payments::expire_request(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			Error::<Test>::PaymentRequestNotExpired
+		);
+
+		run_to_block(6);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRequestExpired {
+			payment_id: PAYMENT_ID,
+		}));
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_err());
+		assert!(PaymentRequestExpiry::<Test>::get(PAYMENT_ID).is_none());
+		assert_err!(
+			Payments::accept_and_pay(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			Error::<Test>::NonExistentStorageValue
+		);
+	})
+}
+
+#[test]
+fn accepted_payment_request_is_not_pruned() {
+	new_test_ext().execute_with(|| {
+		request_invoice(Some(5));
+		assert_ok!(Payments::accept_and_pay(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_err!(
+			Payments::accept_and_pay(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			Error::<Test>::InvalidAction
 		);
+
+		run_to_block(6);
 		assert_eq!(
-			mock::PaymentId::next(&SENDER_ACCOUNT, &PAYMENT_BENEFICIARY),
-			Some(mock::PaymentId(2))
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+	})
+}
+
+#[test]
+fn colliding_payment_ids_are_rejected() {
+	new_test_ext().execute_with(|| {
+		request_invoice(None);
+		// Force the next payment id to collide with the outstanding request
+		LAST_ID.with(|id| id.set(0));
+
+		assert_err!(
+			Payments::request_payment(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				SENDER_ACCOUNT,
+				ASSET_ID,
+				PAYMENT_AMOUNT,
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::PaymentAlreadyInProcess
+		);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::PaymentRequested
+		);
+		assert_eq!(PaymentRequestExpiry::<Test>::get(PAYMENT_ID), Some(101));
+	})
+}
+
+/// Sender(10) is a loyal customer, so the fee handler waives the 2 tokens of
+/// the FEE_SENDER_ACCOUNT(30) and discounts 4 tokens from the payment of 20
+/// tokens. The remaining fees are the same as for a regular payment.
+#[test]
+fn discounts_from_fee_handler_are_respected_on_release() {
+	new_test_ext().execute_with(|| {
+		let remark: BoundedDataOf<Test> = BoundedVec::truncate_from(LOYALTY_REMARK.to_vec());
+		assert_ok!(Payments::pay(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			Some(remark.clone()),
+			None,
+			None,
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentCreated {
+			payment_id: PAYMENT_ID,
+			asset: ASSET_ID,
+			amount: PAYMENT_AMOUNT - LOYALTY_DISCOUNT,
+			remark: Some(remark),
+			arbiter: None,
+		}));
+
+		let payment = PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap();
+		assert_eq!(payment.amount, PAYMENT_AMOUNT - LOYALTY_DISCOUNT);
+		assert_eq!(
+			payment.discounts.into_inner(),
+			vec![
+				Discount {
+					target: DiscountTarget::Fee(Role::Sender, FEE_SENDER_ACCOUNT),
+					amount: FEE_SENDER_AMOUNT,
+				},
+				Discount {
+					target: DiscountTarget::Amount,
+					amount: LOYALTY_DISCOUNT,
+				},
+			]
+		);
+		assert!(!payment
+			.fees
+			.sender_pays
+			.iter()
+			.any(|(account, _, _)| *account == FEE_SENDER_ACCOUNT));
+
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_SENDER_ACCOUNT),
+			0
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_BENEFICIARY_ACCOUNT),
+			FEE_BENEFICIARY_AMOUNT
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_SYSTEM_ACCOUNT),
+			EXPECTED_SYSTEM_TOTAL_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - LOYALTY_DISCOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - (PAYMENT_AMOUNT - LOYALTY_DISCOUNT) - SYSTEM_FEE
+		);
+	})
+}
+
+#[test]
+fn beneficiary_can_grant_discount() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+
+		assert_err!(
+			Payments::grant_discount(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID, 5),
+			Error::<Test>::InvalidBeneficiary
+		);
+		assert_ok!(Payments::grant_discount(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			5
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentDiscountGranted {
+			payment_id: PAYMENT_ID,
+			amount: 5,
+		}));
+
+		let payment = PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap();
+		assert_eq!(payment.amount, PAYMENT_AMOUNT - 5);
+		assert_eq!(
+			payment.discounts.into_inner(),
+			vec![Discount {
+				target: DiscountTarget::Amount,
+				amount: 5,
+			}]
+		);
+
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - 5 - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - (PAYMENT_AMOUNT - 5) - FEE_SENDER_AMOUNT - EXPECTED_SYSTEM_SENDER_FEE
+		);
+	})
+}
+
+#[test]
+fn discounted_payment_is_fully_returned_on_cancel() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::grant_discount(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			5
+		));
+
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID));
+		check_balance_cancellation();
+	})
+}
+
+fn dispute_payment_with_arbiter() {
+	assert_ok!(Payments::pay(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_BENEFICIARY,
+		ASSET_ID,
+		PAYMENT_AMOUNT,
+		None,
+		None,
+		Some(ARBITER_ACCOUNT),
+	));
+	assert_ok!(Payments::request_refund(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_ID
+	));
+	assert_ok!(Payments::dispute_refund(
+		RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+		PAYMENT_ID
+	));
+}
+
+#[test]
+fn arbiter_resolves_dispute_and_gets_incentive() {
+	new_test_ext().execute_with(|| {
+		dispute_payment_with_arbiter();
+		let dispute_result = DisputeResult {
+			percent_beneficiary: Percent::from_percent(90),
+			in_favor_of: Role::Beneficiary,
+		};
+
+		assert_err!(
+			Payments::resolve_dispute(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				dispute_result.clone()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Payments::resolve_dispute(
+			RuntimeOrigin::signed(ARBITER_ACCOUNT),
+			PAYMENT_ID,
+			dispute_result
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentDisputeResolved {
+			payment_id: PAYMENT_ID,
+		}));
+
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ARBITER_ACCOUNT),
+			INCENTIVE_AMOUNT
+		);
+		assert_eq!(<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ROOT_ACCOUNT), 0);
+	})
+}
+
+#[test]
+fn payments_name_their_arbiter() {
+	new_test_ext().execute_with(|| {
+		dispute_payment_with_arbiter();
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentCreated {
+			payment_id: PAYMENT_ID,
+			asset: ASSET_ID,
+			amount: PAYMENT_AMOUNT,
+			remark: None,
+			arbiter: Some(ARBITER_ACCOUNT),
+		}));
+	})
+}
+
+#[test]
+fn parties_can_not_be_the_arbiter_of_their_payment() {
+	new_test_ext().execute_with(|| {
+		for arbiter in [SENDER_ACCOUNT, PAYMENT_BENEFICIARY] {
+			assert_err!(
+				Payments::pay(
+					RuntimeOrigin::signed(SENDER_ACCOUNT),
+					PAYMENT_BENEFICIARY,
+					ASSET_ID,
+					PAYMENT_AMOUNT,
+					None,
+					None,
+					Some(arbiter),
+				),
+				Error::<Test>::InvalidArbiter
+			);
+			assert_err!(
+				Payments::pay_with_hash_lock(
+					RuntimeOrigin::signed(SENDER_ACCOUNT),
+					PAYMENT_BENEFICIARY,
+					ASSET_ID,
+					PAYMENT_AMOUNT,
+					<Test as frame_system::Config>::Hashing::hash(DELIVERY_CODE),
+					10,
+					None,
+					Some(arbiter),
+				),
+				Error::<Test>::InvalidArbiter
+			);
+		}
+	})
+}
+
+#[test]
+fn dispute_resolver_can_not_resolve_disputes_with_arbiter() {
+	new_test_ext().execute_with(|| {
+		dispute_payment_with_arbiter();
+
+		assert_err!(
+			Payments::resolve_dispute(
+				RuntimeOrigin::root(),
+				PAYMENT_ID,
+				DisputeResult {
+					percent_beneficiary: Percent::from_percent(90),
+					in_favor_of: Role::Beneficiary,
+				}
+			),
+			DispatchError::BadOrigin
+		);
+	})
+}
+
+#[test]
+fn default_result_of_disputes_with_arbiter_pays_no_incentive() {
+	new_test_ext().execute_with(|| {
+		dispute_payment_with_arbiter();
+
+		run_to_block(21);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentDisputeResolved {
+			payment_id: PAYMENT_ID,
+		}));
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ARBITER_ACCOUNT),
+			0
+		);
+		assert_eq!(<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ROOT_ACCOUNT), 0);
+	})
+}
+
+#[test]
+fn arbiter_can_not_resolve_disputes_of_other_payments() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_ok!(Payments::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID
+		));
+
+		assert_err!(
+			Payments::resolve_dispute(
+				RuntimeOrigin::signed(ARBITER_ACCOUNT),
+				PAYMENT_ID,
+				DisputeResult {
+					percent_beneficiary: Percent::from_percent(90),
+					in_favor_of: Role::Beneficiary,
+				}
+			),
+			DispatchError::BadOrigin
 		);
+	})
+}
+
+fn deposit_of(who: &AccountId) -> Balance {
+	let reason: RuntimeHoldReason = HoldReason::PaymentDeposit.into();
+	<Balances as fungible::InspectHold<_>>::balance_on_hold(&reason, who)
+}
+
+#[test]
+fn deposit_is_held_while_payment_is_live() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), PAYMENT_DEPOSIT);
+
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+	})
+}
+
+#[test]
+fn deposit_is_released_on_cancel() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID));
+
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+		check_balance_cancellation();
+	})
+}
+
+#[test]
+fn payment_request_deposit_is_held_from_beneficiary() {
+	new_test_ext().execute_with(|| {
+		request_invoice(None);
+		assert_eq!(deposit_of(&PAYMENT_BENEFICIARY), PAYMENT_DEPOSIT);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+
+		assert_ok!(Payments::reject_request(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_eq!(deposit_of(&PAYMENT_BENEFICIARY), 0);
+	})
+}
+
+fn dispute_payment() {
+	let _ = Assets::mint(
+		RuntimeOrigin::signed(ASSET_ADMIN_ACCOUNT),
+		ASSET_ID,
+		PAYMENT_BENEFICIARY,
+		10,
+	);
+	build_payment(ASSERT_PAYMENT_CREATION);
+	assert_ok!(Payments::request_refund(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_ID
+	));
+	assert_ok!(Payments::dispute_refund(
+		RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+		PAYMENT_ID
+	));
+}
+
+#[test]
+fn parties_can_submit_evidence_while_under_review() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_err!(
+			Payments::submit_evidence(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_ID,
+				H256::repeat_byte(1),
+				EvidenceKind::Statement
+			),
+			Error::<Test>::InvalidAction
+		);
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_ok!(Payments::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID
+		));
+
+		assert_err!(
+			Payments::submit_evidence(
+				RuntimeOrigin::signed(999),
+				PAYMENT_ID,
+				H256::repeat_byte(1),
+				EvidenceKind::Statement
+			),
+			Error::<Test>::InvalidAction
+		);
+		assert_ok!(Payments::submit_evidence(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			H256::repeat_byte(1),
+			EvidenceKind::Document
+		));
+		assert_ok!(Payments::submit_evidence(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			H256::repeat_byte(2),
+			EvidenceKind::Media
+		));
+		System::assert_has_event(RuntimeEvent::Payments(
+			pallet_payments::Event::PaymentEvidenceSubmitted {
+				payment_id: PAYMENT_ID,
+				who: SENDER_ACCOUNT,
+				hash: H256::repeat_byte(2),
+				kind: EvidenceKind::Media,
+			},
+		));
+
+		assert_eq!(
+			PaymentEvidence::<Test>::get(PAYMENT_ID).into_inner(),
+			vec![
+				Evidence {
+					submitted_by: Role::Beneficiary,
+					hash: H256::repeat_byte(1),
+					kind: EvidenceKind::Document,
+					submitted_at: 1,
+				},
+				Evidence {
+					submitted_by: Role::Sender,
+					hash: H256::repeat_byte(2),
+					kind: EvidenceKind::Media,
+					submitted_at: 1,
+				},
+			]
+		);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 2 * PAYMENT_DEPOSIT);
+		assert_eq!(deposit_of(&PAYMENT_BENEFICIARY), PAYMENT_DEPOSIT);
+
+		assert_ok!(Payments::resolve_dispute(
+			RuntimeOrigin::root(),
+			PAYMENT_ID,
+			DisputeResult {
+				percent_beneficiary: Percent::from_percent(100),
+				in_favor_of: Role::Beneficiary,
+			}
+		));
+		assert!(PaymentEvidence::<Test>::get(PAYMENT_ID).is_empty());
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+		assert_eq!(deposit_of(&PAYMENT_BENEFICIARY), 0);
+	})
+}
+
+#[test]
+fn evidence_of_a_payment_is_bounded() {
+	new_test_ext().execute_with(|| {
+		dispute_payment();
+		for i in 0..4 {
+			assert_ok!(Payments::submit_evidence(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				H256::repeat_byte(i),
+				EvidenceKind::Communication
+			));
+		}
+		assert_err!(
+			Payments::submit_evidence(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_ID,
+				H256::repeat_byte(4),
+				EvidenceKind::Statement
+			),
+			Error::<Test>::TooMuchEvidence
+		);
+	})
+}
+
+#[test]
+fn default_dispute_result_is_applied_after_review_period() {
+	new_test_ext().execute_with(|| {
+		dispute_payment();
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRefundDisputed {
+			payment_id: PAYMENT_ID,
+			review_deadline: 21,
+		}));
+
+		run_to_block(20);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::NeedsReview
+		);
+
+		run_to_block(21);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentDisputeResolved {
+			payment_id: PAYMENT_ID,
+		}));
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		// Nobody resolved the dispute, so both parties keep their incentive
+		assert_eq!(<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ROOT_ACCOUNT), 0);
+		assert_eq!(
+			<Assets as fungibles::InspectHold<_>>::total_balance_on_hold(ASSET_ID, &SENDER_ACCOUNT),
+			0
+		);
+		assert_eq!(
+			<Assets as fungibles::InspectHold<_>>::total_balance_on_hold(ASSET_ID, &PAYMENT_BENEFICIARY),
+			0
+		);
+	})
+}
+
+#[test]
+fn only_root_applies_the_default_dispute_result() {
+	new_test_ext().execute_with(|| {
+		dispute_payment();
+
+		assert_err!(
+			Payments::apply_default_dispute_result(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Payments::apply_default_dispute_result(
+			RuntimeOrigin::root(),
+			PAYMENT_ID
+		));
+		assert_err!(
+			Payments::apply_default_dispute_result(RuntimeOrigin::root(), PAYMENT_ID),
+			Error::<Test>::InvalidAction
+		);
+	})
+}
+
+#[test]
+fn resolving_dispute_cancels_default_result() {
+	new_test_ext().execute_with(|| {
+		dispute_payment();
+		assert_ok!(Payments::resolve_dispute(
+			RuntimeOrigin::root(),
+			PAYMENT_ID,
+			DisputeResult {
+				percent_beneficiary: Percent::from_percent(100),
+				in_favor_of: Role::Beneficiary,
+			}
+		));
+
+		run_to_block(30);
+		assert!(!System::events().iter().any(|r| matches!(
+			r.event,
+			RuntimeEvent::Scheduler(pallet_scheduler::Event::Dispatched { .. })
+		)));
+	})
+}
+
+#[test]
+fn finished_payments_are_pruned_after_retention() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+		assert!(FinishedPayments::<Test>::contains_key(11, PAYMENT_ID));
+
+		Payments::on_idle(10, Weight::MAX);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+
+		Payments::on_idle(11, Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentPruned {
+			payment_id: PAYMENT_ID,
+		}));
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_err());
+		assert!(PaymentParties::<Test>::get(PAYMENT_ID).is_err());
+		assert!(!FinishedPayments::<Test>::contains_key(11, PAYMENT_ID));
+	})
+}
+
+#[test]
+fn pruning_finished_payments_respects_weight_limit() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+
+		assert_eq!(Payments::on_idle(11, Weight::zero()), Weight::zero());
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_ok());
+	})
+}
+
+#[test]
+fn pruning_finished_payments_skips_those_not_due_yet() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+
+		// Nothing is due, so the payments of later blocks are not even read
+		assert_eq!(Payments::on_idle(10, Weight::MAX), Weight::zero());
+		assert!(FinishedPayments::<Test>::contains_key(11, PAYMENT_ID));
+
+		Payments::on_idle(11, Weight::MAX);
+		assert!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).is_err());
+	})
+}
+
+#[test]
+fn payment_is_released_automatically_after_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payments::pay(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			None,
+			Some(5),
+			None,
+		));
+		System::assert_has_event(RuntimeEvent::Payments(
+			pallet_payments::Event::PaymentAutoReleaseScheduled {
+				payment_id: PAYMENT_ID,
+				release_at: 6,
+			},
+		));
+
+		run_to_block(5);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Created
+		);
+
+		run_to_block(6);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentReleased {
+			payment_id: PAYMENT_ID,
+		}));
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+	})
+}
+
+#[test]
+fn refund_request_prevents_automatic_release() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payments::pay(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			None,
+			Some(5),
+			None,
+		));
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+
+		run_to_block(6);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::RefundRequested { cancel_block: 11 }
+		);
+
+		run_to_block(11);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRefunded {
+			payment_id: PAYMENT_ID,
+		}));
+		check_balance_cancellation();
+	})
+}
+
+/// Sender(10) pays 20 tokens to the PAYMENT_BENEFICIARY(11) in two milestones
+/// of 10 tokens each. Releasing the first milestone settles half of the fees
+/// and incentive, and releasing the second one settles the rest, ending with
+/// the same balances as releasing the whole payment at once.
+#[test]
+fn pay_with_milestones_and_release_works() {
+	new_test_ext().execute_with(|| {
+		let milestones: MilestoneSpecsOf<Test> =
+			BoundedVec::truncate_from(vec![(10, H256::repeat_byte(1)), (10, H256::repeat_byte(2))]);
+
+		assert_ok!(Payments::pay_with_milestones(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			milestones,
+			None,
+			None,
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentCreated {
+			payment_id: PAYMENT_ID,
+			asset: ASSET_ID,
+			amount: PAYMENT_AMOUNT,
+			remark: None,
+			arbiter: None,
+		}));
+
+		assert_ok!(Payments::release_milestone(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			1
+		));
+		System::assert_has_event(RuntimeEvent::Payments(
+			pallet_payments::Event::PaymentMilestoneReleased {
+				payment_id: PAYMENT_ID,
+				milestone: 1,
+				amount: 10,
+			},
+		));
+		assert_err!(
+			Payments::release_milestone(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID, 1),
+			Error::<Test>::MilestoneAlreadyReleased
+		);
+		assert_err!(
+			Payments::release_milestone(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID, 2),
+			Error::<Test>::InvalidMilestone
+		);
+
+		let payment = PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap();
+		assert_eq!(payment.amount, 10);
+		assert_eq!(payment.incentive_amount, INCENTIVE_AMOUNT / 2);
+		assert_eq!(payment.state, PaymentState::Created);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			10 - 1 - 1
+		);
+
+		assert_ok!(Payments::release_milestone(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			0
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentReleased {
+			payment_id: PAYMENT_ID,
+		}));
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		assert!(!PaymentMilestones::<Test>::contains_key(PAYMENT_ID));
+
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_SYSTEM_ACCOUNT),
+			EXPECTED_SYSTEM_TOTAL_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_SENDER_ACCOUNT),
+			FEE_SENDER_AMOUNT
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_BENEFICIARY_ACCOUNT),
+			FEE_BENEFICIARY_AMOUNT
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - PAYMENT_AMOUNT - FEE_SENDER_AMOUNT - SYSTEM_FEE
+		);
+	})
+}
+
+#[test]
+fn pay_with_milestones_fails_with_invalid_milestones() {
+	new_test_ext().execute_with(|| {
+		assert_err!(
+			Payments::pay_with_milestones(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_BENEFICIARY,
+				ASSET_ID,
+				BoundedVec::truncate_from(vec![(0, H256::zero())]),
+				None,
+				None,
+			),
+			Error::<Test>::InvalidMilestone
+		);
+		assert_err!(
+			Payments::pay_with_milestones(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_BENEFICIARY,
+				ASSET_ID,
+				BoundedVec::new(),
+				None,
+				None,
+			),
+			Error::<Test>::InvalidMilestone
+		);
+	})
+}
+
+/// After the first of two milestones is released, a refund only returns the
+/// unreleased balance (and the rest of the sender's fees and incentive).
+#[test]
+fn refund_of_milestone_payment_only_involves_unreleased_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payments::pay_with_milestones(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			BoundedVec::truncate_from(vec![(10, H256::repeat_byte(1)), (10, H256::repeat_byte(2))]),
+			None,
+			None,
+		));
+		assert_ok!(Payments::release_milestone(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			0
+		));
+
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		run_to_block(11);
+
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentRefunded {
+			payment_id: PAYMENT_ID,
+		}));
+		assert!(!PaymentMilestones::<Test>::contains_key(PAYMENT_ID));
+
+		// Only the fees of the released milestone are charged
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_SYSTEM_ACCOUNT),
+			2
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			10 - 1 - 1
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - 10 - 1 - 1
+		);
+	})
+}
+
+const PLATFORM_ACCOUNT: AccountId = 40;
+const REFERRER_ACCOUNT: AccountId = 41;
+
+fn pay_with_splits() {
+	assert_ok!(Payments::pay_with_splits(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_BENEFICIARY,
+		ASSET_ID,
+		PAYMENT_AMOUNT,
+		BoundedVec::truncate_from(vec![
+			(PLATFORM_ACCOUNT, Percent::from_percent(10)),
+			(REFERRER_ACCOUNT, Percent::from_percent(20)),
+		]),
+		None,
+		None,
+	));
+}
+
+#[test]
+fn pay_with_splits_settles_every_share() {
+	new_test_ext().execute_with(|| {
+		pay_with_splits();
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+
+		// Shares are taken from the amount left after the beneficiary's fees (14)
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentSplitSettled {
+			payment_id: PAYMENT_ID,
+			beneficiary: REFERRER_ACCOUNT,
+			amount: 2,
+		}));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			1
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &REFERRER_ACCOUNT),
+			2
+		);
+		// The beneficiary keeps the rest, bearing its fees
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE - 1 - 2
+		);
+		assert!(!PaymentSplits::<Test>::contains_key(PAYMENT_ID));
+	})
+}
+
+#[test]
+fn disputes_apply_to_every_share() {
+	new_test_ext().execute_with(|| {
+		let _ = Assets::mint(
+			RuntimeOrigin::signed(ASSET_ADMIN_ACCOUNT),
+			ASSET_ID,
+			PAYMENT_BENEFICIARY,
+			10,
+		);
+		pay_with_splits();
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_ok!(Payments::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID
+		));
+
+		assert_ok!(Payments::resolve_dispute(
+			RuntimeOrigin::root(),
+			PAYMENT_ID,
+			DisputeResult {
+				percent_beneficiary: Percent::from_percent(80),
+				in_favor_of: Role::Beneficiary,
+			}
+		));
+
+		// Co-beneficiaries get their share of what the beneficiary keeps after
+		// the fees charged on disputes (16 - 3)
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			1
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &REFERRER_ACCOUNT),
+			2
+		);
+	})
+}
+
+#[test]
+fn shares_leave_out_the_optional_fees_waived_on_disputes() {
+	new_test_ext().execute_with(|| {
+		let _ = Assets::mint(
+			RuntimeOrigin::signed(ASSET_ADMIN_ACCOUNT),
+			ASSET_ID,
+			PAYMENT_BENEFICIARY,
+			10,
+		);
+		assert_ok!(Payments::pay_with_splits(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			BoundedVec::truncate_from(vec![
+				(PLATFORM_ACCOUNT, Percent::from_percent(60)),
+				(REFERRER_ACCOUNT, Percent::from_percent(39)),
+			]),
+			None,
+			None,
+		));
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		assert_ok!(Payments::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID
+		));
+
+		assert_ok!(Payments::resolve_dispute(
+			RuntimeOrigin::root(),
+			PAYMENT_ID,
+			DisputeResult {
+				percent_beneficiary: Percent::from_percent(80),
+				in_favor_of: Role::Beneficiary,
+			}
+		));
+
+		// The optional fee of the beneficiary isn't charged on disputes, but
+		// shares are still taken from what is left after it (16 - 3 - 3)
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &FEE_BENEFICIARY_ACCOUNT),
+			0
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			6
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &REFERRER_ACCOUNT),
+			3
+		);
+	})
+}
+
+#[test]
+fn released_milestones_pay_their_shares() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payments::pay_with_milestones(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			BoundedVec::truncate_from(vec![(10, H256::repeat_byte(1)), (10, H256::repeat_byte(2))]),
+			None,
+			None,
+		));
+		PaymentSplits::<Test>::insert(
+			PAYMENT_ID,
+			BoundedVec::truncate_from(vec![(PLATFORM_ACCOUNT, Percent::from_percent(50))]),
+		);
+
+		// Out of the 8 left after the fees of the milestone
+		assert_ok!(Payments::release_milestone(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			0
+		));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			4
+		);
+
+		// Out of the 6 left after the rest of the fees
+		assert_ok!(Payments::release_milestone(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			1
+		));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			4 + 3
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE - 4 - 3
+		);
+		assert!(!PaymentSplits::<Test>::contains_key(PAYMENT_ID));
+	})
+}
+
+#[test]
+fn splits_can_take_almost_everything_the_beneficiary_keeps() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payments::pay_with_splits(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			BoundedVec::truncate_from(vec![
+				(PLATFORM_ACCOUNT, Percent::from_percent(60)),
+				(REFERRER_ACCOUNT, Percent::from_percent(39)),
+			]),
+			None,
+			None,
+		));
+		assert_ok!(Payments::release(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID));
+
+		// Out of the 14 left after fees
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			8
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &REFERRER_ACCOUNT),
+			5
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			1
+		);
+	})
+}
+
+#[test]
+fn refunded_split_payment_pays_no_shares() {
+	new_test_ext().execute_with(|| {
+		pay_with_splits();
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID));
+
+		assert!(!PaymentSplits::<Test>::contains_key(PAYMENT_ID));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PLATFORM_ACCOUNT),
+			0
+		);
+		check_balance_cancellation();
+	})
+}
+
+#[test]
+fn pay_with_splits_fails_with_invalid_splits() {
+	new_test_ext().execute_with(|| {
+		for splits in [
+			vec![],
+			vec![(PLATFORM_ACCOUNT, Percent::zero())],
+			vec![
+				(PLATFORM_ACCOUNT, Percent::from_percent(60)),
+				(REFERRER_ACCOUNT, Percent::from_percent(40)),
+			],
+		] {
+			assert_err!(
+				Payments::pay_with_splits(
+					RuntimeOrigin::signed(SENDER_ACCOUNT),
+					PAYMENT_BENEFICIARY,
+					ASSET_ID,
+					PAYMENT_AMOUNT,
+					BoundedVec::truncate_from(splits),
+					None,
+					None,
+				),
+				Error::<Test>::InvalidSplit
+			);
+		}
+	})
+}
+
+const DELIVERY_CODE: &[u8] = b"delivery-code";
+
+fn pay_with_hash_lock() {
+	assert_ok!(Payments::pay_with_hash_lock(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_BENEFICIARY,
+		ASSET_ID,
+		PAYMENT_AMOUNT,
+		<Test as frame_system::Config>::Hashing::hash(DELIVERY_CODE),
+		10,
+		None,
+		None,
+	));
+}
+
+#[test]
+fn hash_locked_payment_is_claimed_with_preimage() {
+	new_test_ext().execute_with(|| {
+		pay_with_hash_lock();
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentHashLocked {
+			payment_id: PAYMENT_ID,
+			hash: <Test as frame_system::Config>::Hashing::hash(DELIVERY_CODE),
+			timeout: 11,
+		}));
+
+		assert_err!(
+			Payments::claim_payment(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				BoundedVec::truncate_from(b"wrong-code".to_vec())
+			),
+			Error::<Test>::InvalidPreimage
+		);
+		assert_err!(
+			Payments::claim_payment(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_ID,
+				BoundedVec::truncate_from(DELIVERY_CODE.to_vec())
+			),
+			Error::<Test>::InvalidBeneficiary
+		);
+		assert_ok!(Payments::claim_payment(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			BoundedVec::truncate_from(DELIVERY_CODE.to_vec())
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentClaimed {
+			payment_id: PAYMENT_ID,
+			preimage: BoundedVec::truncate_from(DELIVERY_CODE.to_vec()),
+		}));
+
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+		assert!(!PaymentHashLocks::<Test>::contains_key(PAYMENT_ID));
+
+		// The refund is no longer scheduled
+		run_to_block(11);
+		assert!(!System::events().iter().any(|r| matches!(
+			r.event,
+			RuntimeEvent::Scheduler(pallet_scheduler::Event::Dispatched { .. })
+		)));
+	})
+}
+
+#[test]
+fn hash_locked_payment_is_refunded_after_timeout() {
+	new_test_ext().execute_with(|| {
+		pay_with_hash_lock();
+		assert_err!(
+			Payments::request_refund(RuntimeOrigin::signed(SENDER_ACCOUNT), PAYMENT_ID),
+			Error::<Test>::InvalidAction
+		);
+
+		run_to_block(11);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentCancelled {
+			payment_id: PAYMENT_ID,
+		}));
+		assert!(!PaymentHashLocks::<Test>::contains_key(PAYMENT_ID));
+		check_balance_cancellation();
+	})
+}
+
+#[test]
+fn parties_agree_on_a_partial_refund() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+
+		assert_ok!(Payments::propose_partial_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			Percent::from_percent(60),
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PartialRefundProposed {
+			payment_id: PAYMENT_ID,
+			proposed_by: Role::Beneficiary,
+			beneficiary_share: Percent::from_percent(60),
+		}));
+
+		// The proposer can't accept its own proposal
+		assert_err!(
+			Payments::accept_partial_refund(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				Percent::from_percent(60)
+			),
+			Error::<Test>::InvalidAction
+		);
+		assert_ok!(Payments::accept_partial_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			Percent::from_percent(60)
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PartialRefundAccepted {
+			payment_id: PAYMENT_ID,
+			refund: 8,
+		}));
+
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+			PaymentState::Finished
+		);
+		assert!(!RefundProposals::<Test>::contains_key(PAYMENT_ID));
+
+		// No incentive is paid, as there's no dispute to resolve, and fees are
+		// only charged on the 60% the beneficiary keeps (rounding down the
+		// refunded part of each fee)
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - PAYMENT_AMOUNT + 8 - FEE_SENDER_AMOUNT - (SYSTEM_FEE - 1)
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - 8 - (FEE_BENEFICIARY_AMOUNT - 1) - (SYSTEM_FEE - 1)
+		);
+		assert_eq!(<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &ROOT_ACCOUNT), 0);
+
+		// The refund is no longer scheduled
+		run_to_block(20);
+		assert!(!System::events().iter().any(|r| matches!(
+			r.event,
+			RuntimeEvent::Payments(pallet_payments::Event::PaymentCancelled { .. })
+		)));
+	})
+}
+
+fn accept_partial_refund(beneficiary_share: Percent) {
+	assert_ok!(Payments::propose_partial_refund(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_ID,
+		beneficiary_share,
+	));
+	assert_ok!(Payments::accept_partial_refund(
+		RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+		PAYMENT_ID,
+		beneficiary_share
+	));
+	assert_eq!(
+		PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap().state,
+		PaymentState::Finished
+	);
+}
+
+#[test]
+fn partial_refund_of_everything_charges_no_fees() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		accept_partial_refund(Percent::zero());
+
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PartialRefundAccepted {
+			payment_id: PAYMENT_ID,
+			refund: PAYMENT_AMOUNT,
+		}));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			0
+		);
+		assert_eq!(
+			<Assets as fungibles::InspectHold<_>>::total_balance_on_hold(ASSET_ID, &SENDER_ACCOUNT),
+			0
+		);
+		assert_eq!(
+			<Assets as fungibles::InspectHold<_>>::total_balance_on_hold(ASSET_ID, &PAYMENT_BENEFICIARY),
+			0
+		);
+	})
+}
+
+#[test]
+fn partial_refund_of_nothing_settles_like_a_release() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		accept_partial_refund(Percent::one());
+
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PartialRefundAccepted {
+			payment_id: PAYMENT_ID,
+			refund: 0,
+		}));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - PAYMENT_AMOUNT - FEE_SENDER_AMOUNT - SYSTEM_FEE
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+	})
+}
+
+#[test]
+fn partial_refund_must_match_the_latest_proposal() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+
+		assert_err!(
+			Payments::accept_partial_refund(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				Percent::from_percent(50)
+			),
+			Error::<Test>::InvalidRefundProposal
+		);
+		assert_err!(
+			Payments::propose_partial_refund(
+				RuntimeOrigin::signed(ARBITER_ACCOUNT),
+				PAYMENT_ID,
+				Percent::from_percent(50)
+			),
+			Error::<Test>::InvalidAction
+		);
+
+		assert_ok!(Payments::propose_partial_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			Percent::from_percent(50),
+		));
+		// A counter-proposal replaces the previous one
+		assert_ok!(Payments::propose_partial_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PAYMENT_ID,
+			Percent::from_percent(70),
+		));
+		assert_err!(
+			Payments::accept_partial_refund(
+				RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+				PAYMENT_ID,
+				Percent::from_percent(50)
+			),
+			Error::<Test>::InvalidAction
+		);
+		assert_err!(
+			Payments::accept_partial_refund(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_ID,
+				Percent::from_percent(50)
+			),
+			Error::<Test>::InvalidRefundProposal
+		);
+		assert_ok!(Payments::accept_partial_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID,
+			Percent::from_percent(70)
+		));
+	})
+}
+
+fn subscribe(amount: Balance, periods: u32) {
+	assert_ok!(Payments::subscribe(
+		RuntimeOrigin::signed(SENDER_ACCOUNT),
+		PAYMENT_BENEFICIARY,
+		ASSET_ID,
+		amount,
+		10,
+		periods,
+	));
+}
+
+/// Every period of a subscription is a payment held until the end of the
+/// period, when it's released to the beneficiary.
+#[test]
+fn subscription_is_pulled_every_period() {
+	new_test_ext().execute_with(|| {
+		subscribe(PAYMENT_AMOUNT, 2);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::SubscriptionCreated {
+			subscription_id: PAYMENT_ID,
+			beneficiary: PAYMENT_BENEFICIARY,
+			asset: ASSET_ID,
+			amount: PAYMENT_AMOUNT,
+			period: 10,
+			periods: 2,
+		}));
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), PAYMENT_DEPOSIT);
+		assert_err!(
+			Payments::pull_subscription(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID),
+			Error::<Test>::SubscriptionPullNotDue
+		);
+
+		run_to_block(2);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::SubscriptionPulled {
+			subscription_id: PAYMENT_ID,
+			payment_id: PaymentId(2),
+			amount: PAYMENT_AMOUNT,
+		}));
+		System::assert_has_event(RuntimeEvent::Payments(
+			pallet_payments::Event::PaymentAutoReleaseScheduled {
+				payment_id: PaymentId(2),
+				release_at: 12,
+			},
+		));
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PaymentId(2)).map(|payment| payment.state),
+			Ok(PaymentState::Created)
+		);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 2 * PAYMENT_DEPOSIT);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			0
+		);
+
+		// The first period is released as the second one is pulled
+		run_to_block(12);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentReleased {
+			payment_id: PaymentId(2),
+		}));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::SubscriptionFinished {
+			subscription_id: PAYMENT_ID,
+		}));
+		assert!(!Subscriptions::<Test>::contains_key(PAYMENT_ID));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE
+		);
+
+		run_to_block(22);
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::PaymentReleased {
+			payment_id: PaymentId(3),
+		}));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &PAYMENT_BENEFICIARY),
+			2 * (PAYMENT_AMOUNT - FEE_BENEFICIARY_AMOUNT - SYSTEM_FEE)
+		);
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - 2 * (PAYMENT_AMOUNT + FEE_SENDER_AMOUNT + SYSTEM_FEE)
+		);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+	})
+}
+
+#[test]
+fn sender_can_dispute_the_payment_of_a_period() {
+	new_test_ext().execute_with(|| {
+		subscribe(PAYMENT_AMOUNT, 2);
+		run_to_block(2);
+		// The beneficiary holds its incentive when disputing
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(ASSET_ADMIN_ACCOUNT),
+			ASSET_ID,
+			PAYMENT_BENEFICIARY,
+			INCENTIVE_AMOUNT + 1
+		));
+
+		assert_ok!(Payments::request_refund(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PaymentId(2)
+		));
+		assert_ok!(Payments::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_BENEFICIARY),
+			PaymentId(2)
+		));
+
+		// The disputed period isn't released at the end of the period
+		run_to_block(12);
+		assert_eq!(
+			PaymentStore::<Test>::get(SENDER_ACCOUNT, PaymentId(2)).map(|payment| payment.state),
+			Ok(PaymentState::NeedsReview)
+		);
+	})
+}
+
+#[test]
+fn sender_can_cancel_subscription() {
+	new_test_ext().execute_with(|| {
+		subscribe(PAYMENT_AMOUNT, 2);
+		run_to_block(2);
+
+		assert_err!(
+			Payments::cancel_subscription(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID),
+			Error::<Test>::InvalidAction
+		);
+		assert_ok!(Payments::cancel_subscription(
+			RuntimeOrigin::signed(SENDER_ACCOUNT),
+			PAYMENT_ID
+		));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::SubscriptionCancelled {
+			subscription_id: PAYMENT_ID,
+		}));
+		// Only the deposit of the payment of the pulled period is left
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), PAYMENT_DEPOSIT);
+
+		// The pulled period is still released, but no more periods are pulled
+		run_to_block(22);
+		assert!(!Subscriptions::<Test>::contains_key(PAYMENT_ID));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - PAYMENT_AMOUNT - FEE_SENDER_AMOUNT - SYSTEM_FEE
+		);
+		assert_eq!(deposit_of(&SENDER_ACCOUNT), 0);
+	})
+}
+
+/// Sender(10) subscribes to pay 20 tokens (plus 5 tokens of fees and 2 of
+/// incentive) for two periods, but can't afford the second one after giving
+/// most of its balance away.
+#[test]
+fn missed_subscription_pulls_are_reported() {
+	new_test_ext().execute_with(|| {
+		subscribe(PAYMENT_AMOUNT, 2);
+		run_to_block(2);
+		assert_ok!(<Assets as fungibles::Mutate<_>>::transfer(
+			ASSET_ID,
+			&SENDER_ACCOUNT,
+			&ASSET_ADMIN_ACCOUNT,
+			70,
+			frame_support::traits::tokens::Preservation::Expendable,
+		));
+
+		run_to_block(12);
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Payments(pallet_payments::Event::SubscriptionPullFailed {
+				subscription_id: PAYMENT_ID,
+				..
+			})
+		)));
+		System::assert_has_event(RuntimeEvent::Payments(pallet_payments::Event::SubscriptionFinished {
+			subscription_id: PAYMENT_ID,
+		}));
+		assert_eq!(
+			<Assets as fungibles::Inspect<_>>::balance(ASSET_ID, &SENDER_ACCOUNT),
+			INITIAL_BALANCE - 70 - (PAYMENT_AMOUNT + FEE_SENDER_AMOUNT + SYSTEM_FEE)
+		);
+	})
+}
+
+#[test]
+fn payments_get_discounts_by_migration() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		let payment = PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID).unwrap();
+
+		// A payment stored before discounts were recorded
+		frame_support::storage::unhashed::put(
+			&PaymentStore::<Test>::hashed_key_for(SENDER_ACCOUNT, PAYMENT_ID),
+			&(
+				payment.asset,
+				payment.amount,
+				payment.beneficiary,
+				payment.incentive_amount,
+				payment.state.clone(),
+				payment.fees.clone(),
+			),
+		);
+		StorageVersion::new(0).put::<Payments>();
+
+		crate::migrations::v1::AddDiscounts::<Test>::on_runtime_upgrade();
+		assert_eq!(PaymentStore::<Test>::get(SENDER_ACCOUNT, PAYMENT_ID), Ok(payment));
+		assert_eq!(Payments::on_chain_storage_version(), 1);
+	})
+}
+
+#[test]
+fn quote_includes_fees_incentive_and_discounts() {
+	new_test_ext().execute_with(|| {
+		let quote = Payments::quote(&SENDER_ACCOUNT, PAYMENT_BENEFICIARY, ASSET_ID, PAYMENT_AMOUNT, None).unwrap();
+		assert_eq!(quote.amount, PAYMENT_AMOUNT);
+		assert_eq!(quote.incentive, INCENTIVE_AMOUNT);
+		assert_eq!(
+			quote.sender_total,
+			PAYMENT_AMOUNT + INCENTIVE_AMOUNT + FEE_SENDER_AMOUNT + SYSTEM_FEE
+		);
+		assert_eq!(quote.sender_fees.len(), 2);
+		assert_eq!(quote.beneficiary_fees.len(), 2);
+		assert!(quote.discounts.is_empty());
+
+		// The loyalty discount waives the fee of the sender and discounts the amount
+		let quote = Payments::quote(
+			&SENDER_ACCOUNT,
+			PAYMENT_BENEFICIARY,
+			ASSET_ID,
+			PAYMENT_AMOUNT,
+			Some(LOYALTY_REMARK),
+		)
+		.unwrap();
+		assert_eq!(quote.amount, PAYMENT_AMOUNT - LOYALTY_DISCOUNT);
+		assert_eq!(
+			quote.sender_total,
+			PAYMENT_AMOUNT - LOYALTY_DISCOUNT
+				+ INCENTIVE_PERCENTAGE as u64 * (PAYMENT_AMOUNT - LOYALTY_DISCOUNT) / 100
+				+ SYSTEM_FEE
+		);
+		assert_eq!(quote.discounts.len(), 2);
+
+		// Quoting doesn't create a payment
+		assert!(Payments::payments_by_sender(&SENDER_ACCOUNT, None, u32::MAX).is_empty());
+	})
+}
+
+#[test]
+fn payments_are_listed_by_sender_and_beneficiary() {
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+		let payment = Payments::payment_detail(PAYMENT_ID).unwrap();
+
+		assert_eq!(
+			Payments::payments_by_sender(&SENDER_ACCOUNT, None, u32::MAX),
+			vec![(PAYMENT_ID, payment.clone())]
+		);
+		assert_eq!(
+			Payments::payments_by_beneficiary(&PAYMENT_BENEFICIARY, None, u32::MAX),
+			vec![(PAYMENT_ID, payment)]
+		);
+		assert!(Payments::payments_by_beneficiary(&SENDER_ACCOUNT, None, u32::MAX).is_empty());
+
+		assert_ok!(Payments::cancel(RuntimeOrigin::signed(PAYMENT_BENEFICIARY), PAYMENT_ID));
+		assert!(Payments::payment_detail(PAYMENT_ID).is_none());
+		assert!(Payments::payments_by_beneficiary(&PAYMENT_BENEFICIARY, None, u32::MAX).is_empty());
+		assert!(!PaymentsByBeneficiary::<Test>::contains_key(
+			PAYMENT_BENEFICIARY,
+			PAYMENT_ID
+		));
+	})
+}
+
+#[test]
+fn payments_are_listed_in_pages() {
+	new_test_ext().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(Payments::pay(
+				RuntimeOrigin::signed(SENDER_ACCOUNT),
+				PAYMENT_BENEFICIARY,
+				ASSET_ID,
+				PAYMENT_AMOUNT,
+				None,
+				None,
+				None,
+			));
+		}
+
+		let page_ids =
+			|page: Vec<(PaymentId, PaymentDetail<Test>)>| page.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+		let all = page_ids(Payments::payments_by_sender(&SENDER_ACCOUNT, None, u32::MAX));
+		assert_eq!(all.len(), 3);
+
+		let first_page = page_ids(Payments::payments_by_sender(&SENDER_ACCOUNT, None, 2));
+		assert_eq!(first_page, all[..2]);
+		let last_page = page_ids(Payments::payments_by_sender(&SENDER_ACCOUNT, Some(all[1]), 2));
+		assert_eq!(last_page, all[2..]);
+
+		let all = page_ids(Payments::payments_by_beneficiary(&PAYMENT_BENEFICIARY, None, u32::MAX));
+		assert_eq!(all.len(), 3);
+		let first_page = page_ids(Payments::payments_by_beneficiary(&PAYMENT_BENEFICIARY, None, 1));
+		assert_eq!(first_page, all[..1]);
+		let last_page = page_ids(Payments::payments_by_beneficiary(
+			&PAYMENT_BENEFICIARY,
+			Some(all[0]),
+			u32::MAX,
+		));
+		assert_eq!(last_page, all[1..]);
+	})
+}
+
+#[test]
+fn payments_are_indexed_by_beneficiary_by_migration() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		build_payment(ASSERT_PAYMENT_CREATION);
+
+		// A payment created before payments were indexed by beneficiary
+		PaymentsByBeneficiary::<Test>::remove(PAYMENT_BENEFICIARY, PAYMENT_ID);
+		StorageVersion::new(1).put::<Payments>();
+
+		crate::migrations::v2::IndexByBeneficiary::<Test>::on_runtime_upgrade();
+		assert!(PaymentsByBeneficiary::<Test>::contains_key(
+			PAYMENT_BENEFICIARY,
+			PAYMENT_ID
+		));
+		assert_eq!(Payments::on_chain_storage_version(), 2);
+	})
+}
+
+#[test]
+fn next_id_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			mock::PaymentId::next(&SENDER_ACCOUNT, &PAYMENT_BENEFICIARY),
+			Some(mock::PaymentId(1))
+		);
+		assert_eq!(
+			mock::PaymentId::next(&SENDER_ACCOUNT, &PAYMENT_BENEFICIARY),
+			Some(mock::PaymentId(2))
+		);
+	});
+}
+
+#[test]
+fn next_payment_index_is_unique_within_a_block() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Payments::next_payment_index(), Some(0));
+		assert_eq!(Payments::next_payment_index(), Some(1));
+
+		run_to_block(2);
+		assert_eq!(Payments::next_payment_index(), Some(0));
+		assert_eq!(Payments::next_payment_index(), Some(1));
 	});
 }
 
@@ -581,9 +2446,42 @@ fn weights() {
 		("cancel", SubstrateWeight::<Test>::cancel()),
 		("request_refund", SubstrateWeight::<Test>::request_refund()),
 		("dispute_refund", SubstrateWeight::<Test>::dispute_refund()),
+		("submit_evidence", SubstrateWeight::<Test>::submit_evidence()),
+		(
+			"pay_with_hash_lock (20)",
+			SubstrateWeight::<Test>::pay_with_hash_lock(20_u32),
+		),
+		("claim_payment", SubstrateWeight::<Test>::claim_payment()),
+		(
+			"propose_partial_refund",
+			SubstrateWeight::<Test>::propose_partial_refund(),
+		),
+		(
+			"accept_partial_refund",
+			SubstrateWeight::<Test>::accept_partial_refund(),
+		),
 		("resolve_dispute", SubstrateWeight::<Test>::resolve_dispute()),
-		("request_payment", SubstrateWeight::<Test>::request_payment()),
+		("request_payment (20)", SubstrateWeight::<Test>::request_payment(20_u32)),
 		("accept_and_pay", SubstrateWeight::<Test>::accept_and_pay()),
+		(
+			"pay_with_milestones (20, 20)",
+			SubstrateWeight::<Test>::pay_with_milestones(20_u32, 20_u32),
+		),
+		("release_milestone", SubstrateWeight::<Test>::release_milestone()),
+		(
+			"pay_with_splits (20, 10)",
+			SubstrateWeight::<Test>::pay_with_splits(20_u32, 10_u32),
+		),
+		("subscribe", SubstrateWeight::<Test>::subscribe()),
+		("pull_subscription", SubstrateWeight::<Test>::pull_subscription()),
+		("cancel_subscription", SubstrateWeight::<Test>::cancel_subscription()),
+		("reject_request", SubstrateWeight::<Test>::reject_request()),
+		("expire_request", SubstrateWeight::<Test>::expire_request()),
+		("grant_discount", SubstrateWeight::<Test>::grant_discount()),
+		(
+			"prune_finished_payment",
+			SubstrateWeight::<Test>::prune_finished_payment(),
+		),
 	] {
 		println!("{function}: {weight:?}",);
 		println!(
@@ -4,7 +4,7 @@ use crate::*;
 use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Zero, BoundedVec, Percent, Saturating};
+use sp_runtime::{traits::Zero, BoundedVec, Perbill, Percent, Saturating};
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 // This pallet's asset id and balance type.
@@ -21,6 +21,18 @@ pub type FeeDetails<T> = BoundedVec<Fee<T>, MaxFeesOf<T>>;
 pub type CallOf<T> = <T as Config>::RuntimeCall;
 pub type PreimagesOf<T> = <T as Config>::Preimages;
 pub type BoundedCallOf<T> = Bounded<CallOf<T>, <T as frame_system::Config>::Hashing>;
+pub type SubscriptionOf<T> = Subscription<AccountIdOf<T>, AssetIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>;
+pub type MilestoneOf<T> = Milestone<BalanceOf<T>, <T as frame_system::Config>::Hash>;
+pub type MilestonesOf<T> = BoundedVec<MilestoneOf<T>, <T as Config>::MaxMilestones>;
+pub type HashLockOf<T> = HashLock<<T as frame_system::Config>::Hash, BlockNumberFor<T>>;
+pub type FeeQuoteOf<T> = FeeQuote<AccountIdOf<T>, BalanceOf<T>>;
+pub type SplitsOf<T> = BoundedVec<(AccountIdOf<T>, Percent), <T as Config>::MaxSplits>;
+pub type DiscountOf<T> = Discount<AccountIdOf<T>, BalanceOf<T>>;
+pub type DiscountsOf<T> = BoundedVec<DiscountOf<T>, <T as Config>::MaxDiscounts>;
+pub type EvidenceOf<T> = Evidence<<T as frame_system::Config>::Hash, BlockNumberFor<T>>;
+pub type EvidenceListOf<T> = BoundedVec<EvidenceOf<T>, <T as Config>::MaxEvidence>;
+pub type MilestoneSpecsOf<T> =
+	BoundedVec<(BalanceOf<T>, <T as frame_system::Config>::Hash), <T as Config>::MaxMilestones>;
 
 /// The PaymentDetail struct stores information about the payment
 /// A "payment" is similar to an escrow, it is used to guarantee proof of
@@ -45,6 +57,33 @@ pub struct PaymentDetail<T: pallet::Config> {
 	pub state: PaymentState<BlockNumberFor<T>>,
 	/// fee charged and recipient account details
 	pub fees: Fees<T>,
+	/// discounts already applied to the amount or the fees of the payment
+	pub discounts: DiscountsOf<T>,
+}
+
+impl<T: pallet::Config> PaymentDetail<T> {
+	/// Applies a discount to the amount or the fee it targets, and records it
+	/// along with the payment. Returns the amount that was actually discounted,
+	/// which is never more than what the targeted line amounts to.
+	pub fn apply_discount(&mut self, discount: DiscountOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let Discount { target, amount } = discount;
+		let discounted = match &target {
+			DiscountTarget::Amount => {
+				let discounted = amount.min(self.amount);
+				self.amount.saturating_reduce(discounted);
+				discounted
+			}
+			DiscountTarget::Fee(role, recipient) => self.fees.discount(role, recipient, amount),
+		};
+
+		self.discounts
+			.try_push(Discount {
+				target,
+				amount: discounted,
+			})
+			.map_err(|_| Error::<T>::TooManyDiscounts)?;
+		Ok(discounted)
+	}
 }
 
 /// The `PaymentState` enum tracks the possible states that a payment can be in.
@@ -65,6 +104,123 @@ pub enum PaymentState<BlockNumber> {
 	Finished,
 }
 
+/// A stage of a payment that is released to the beneficiary on its own once
+/// the sender considers it fulfilled.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Milestone<Balance, Hash> {
+	/// amount released to the beneficiary for this milestone
+	pub amount: Balance,
+	/// hash of the description of the milestone agreed upon by both parties
+	pub description: Hash,
+	/// whether the milestone has already been released
+	pub released: bool,
+}
+
+/// A periodic payment the sender authorized to a beneficiary. Every period, a
+/// regular payment of the amount is created and held until the end of the
+/// period, until no periods remain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Subscription<AccountId, AssetId, Balance, BlockNumber> {
+	/// the creator of the subscription, who pays every period
+	pub sender: AccountId,
+	/// the recipient of the payments
+	pub beneficiary: AccountId,
+	/// type of asset used for payments
+	pub asset: AssetId,
+	/// amount paid every period
+	pub amount: Balance,
+	/// amount of blocks between payments
+	pub period: BlockNumber,
+	/// amount of payments left to be pulled
+	pub remaining_periods: u32,
+	/// block from which the next payment can be pulled
+	pub next_pull_at: BlockNumber,
+}
+
+/// The condition for the beneficiary of a payment to be paid: revealing the
+/// preimage of `hash` before `timeout`, after which it's refunded.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct HashLock<Hash, BlockNumber> {
+	/// hash of the secret the beneficiary has to reveal
+	pub hash: Hash,
+	/// block at which the payment is refunded if it wasn't claimed
+	pub timeout: BlockNumber,
+}
+
+/// What a prospective payment would be charged
+#[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, Debug)]
+pub struct FeeQuote<AccountId, Balance> {
+	/// amount paid to the beneficiary, after discounts
+	pub amount: Balance,
+	/// amount held from the sender, returned to it unless it loses a dispute
+	pub incentive: Balance,
+	/// total the sender needs to pay: the amount, its fees and the incentive
+	pub sender_total: Balance,
+	/// fees charged to the sender
+	pub sender_fees: Vec<(AccountId, Balance, ChargableOnDisputes)>,
+	/// fees charged to the beneficiary
+	pub beneficiary_fees: Vec<(AccountId, Balance, ChargableOnDisputes)>,
+	/// discounts applied to the amount or to the fees
+	pub discounts: Vec<Discount<AccountId, Balance>>,
+}
+
+/// A partial refund proposed by a party of a payment, which settles it once
+/// the other party accepts it
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct RefundProposal {
+	/// the party that proposed the refund
+	pub proposed_by: Role,
+	/// share of the amount kept by the beneficiary, the rest being refunded
+	pub beneficiary_share: Percent,
+}
+
+/// What a discount is taken from
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub enum DiscountTarget<AccountId> {
+	/// the amount paid to the beneficiary
+	Amount,
+	/// the fees charged by the recipient to the given role
+	Fee(Role, AccountId),
+}
+
+/// A discount line (e.g. a coupon code, a loyalty or a membership discount)
+/// that reduces the amount of a payment or one of its fees.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Discount<AccountId, Balance> {
+	/// the line the discount is taken from
+	pub target: DiscountTarget<AccountId>,
+	/// amount discounted
+	pub amount: Balance,
+}
+
+/// What a piece of evidence submitted for a dispute consists of
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub enum EvidenceKind {
+	/// a written statement of the submitter's case
+	Statement,
+	/// a receipt, invoice, contract or similar document
+	Document,
+	/// a picture, video or recording
+	Media,
+	/// messages exchanged between the parties
+	Communication,
+	Other,
+}
+
+/// A piece of evidence submitted by one of the parties of a payment under
+/// review. Only the hash of its contents is kept on chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Evidence<Hash, BlockNumber> {
+	/// the party that submitted the evidence
+	pub submitted_by: Role,
+	/// hash of the contents of the evidence
+	pub hash: Hash,
+	/// what the evidence consists of
+	pub kind: EvidenceKind,
+	/// block at which the evidence was submitted
+	pub submitted_at: BlockNumber,
+}
+
 /// Fee Handler trait that defines how to handle marketplace fees to every
 /// payment/swap
 pub trait FeeHandler<T: pallet::Config> {
@@ -76,6 +232,18 @@ pub trait FeeHandler<T: pallet::Config> {
 		amount: &BalanceOf<T>,
 		remark: Option<&[u8]>,
 	) -> Fees<T>;
+
+	/// Get the discounts that apply to a payment, which are taken from its
+	/// amount or the fees returned by `apply_fees`
+	fn apply_discounts(
+		_asset: &AssetIdOf<T>,
+		_sender: &T::AccountId,
+		_beneficiary: &T::AccountId,
+		_amount: &BalanceOf<T>,
+		_remark: Option<&[u8]>,
+	) -> DiscountsOf<T> {
+		Default::default()
+	}
 }
 
 impl<T: pallet::Config> FeeHandler<T> for () {
@@ -116,6 +284,47 @@ impl<T: pallet::Config> Fees<T> {
 		Self::get_summary(fees, is_dispute)
 	}
 
+	/// Splits off the given portion of every fee, leaving the rest in place.
+	/// Fees that amount to nothing for the portion are left out.
+	pub fn split_off(&mut self, portion: Perbill) -> Fees<T> {
+		let split = |fees: &mut FeeDetails<T>| -> FeeDetails<T> {
+			let split_fees = fees
+				.iter_mut()
+				.filter_map(|(account, fee, charge_dispute)| {
+					let part = portion.mul_floor(*fee);
+					fee.saturating_reduce(part);
+					(!part.is_zero()).then(|| (account.clone(), part, *charge_dispute))
+				})
+				.collect::<Vec<_>>();
+			BoundedVec::truncate_from(split_fees)
+		};
+
+		Fees {
+			sender_pays: split(&mut self.sender_pays),
+			beneficiary_pays: split(&mut self.beneficiary_pays),
+		}
+	}
+
+	/// Discounts up to the given amount from the fees charged by the
+	/// recipient to the given role, dropping the fees that are fully
+	/// discounted. Returns the amount that was actually discounted.
+	pub fn discount(&mut self, role: &Role, recipient: &AccountIdOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		let fees = match role {
+			Role::Sender => &mut self.sender_pays,
+			Role::Beneficiary => &mut self.beneficiary_pays,
+		};
+
+		let mut remaining = amount;
+		for (_, fee, _) in fees.iter_mut().filter(|(account, _, _)| account == recipient) {
+			let part = remaining.min(*fee);
+			fee.saturating_reduce(part);
+			remaining.saturating_reduce(part);
+		}
+		fees.retain(|(_, fee, _)| !fee.is_zero());
+
+		amount.saturating_sub(remaining)
+	}
+
 	fn get_summary(fees: &FeeDetails<T>, is_dispute: bool) -> Result<FeesSummaryForRole<T>, DispatchError> {
 		let mut fees_per_account: BTreeMap<AccountIdOf<T>, Fee<T>> = BTreeMap::new();
 		let mut total_to_discount: BalanceOf<T> = Zero::zero();
@@ -181,6 +181,46 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_payments::runtime_api::PaymentsApi<
+		Block,
+		AccountId,
+		AssetIdOf<Runtime>,
+		Balance,
+		virto_common::PaymentId,
+		PaymentDetail<Runtime>,
+	> for Runtime
+	{
+		fn quote(
+			sender: AccountId,
+			beneficiary: AccountId,
+			asset: AssetIdOf<Runtime>,
+			amount: Balance,
+			remark: Option<Vec<u8>>,
+		) -> Option<FeeQuote<AccountId, Balance>> {
+			Payments::quote(&sender, beneficiary, asset, amount, remark.as_deref()).ok()
+		}
+
+		fn payments_by_sender(
+			sender: AccountId,
+			start: Option<virto_common::PaymentId>,
+			limit: u32,
+		) -> Vec<(virto_common::PaymentId, PaymentDetail<Runtime>)> {
+			Payments::payments_by_sender(&sender, start, limit)
+		}
+
+		fn payments_by_beneficiary(
+			beneficiary: AccountId,
+			start: Option<virto_common::PaymentId>,
+			limit: u32,
+		) -> Vec<(virto_common::PaymentId, PaymentDetail<Runtime>)> {
+			Payments::payments_by_beneficiary(&beneficiary, start, limit)
+		}
+
+		fn payment(payment_id: virto_common::PaymentId) -> Option<PaymentDetail<Runtime>> {
+			Payments::payment_detail(payment_id)
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
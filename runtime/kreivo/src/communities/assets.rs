@@ -0,0 +1,62 @@
+use super::*;
+
+use frame_support::traits::EnsureOrigin;
+
+/// The namespace of the assets created before communities could create their
+/// own.
+const RESERVED_ASSET_NAMESPACE: CommunityId = 0;
+
+/// Assets issued by a community live in the [`FungibleAssetLocation::Here`]
+/// space, where the upper half of the index is the ID of the community and the
+/// lower half is an index chosen freely by the community. This way, one
+/// community can't create an asset within the namespace of another.
+///
+/// The first namespace (i.e. `Here(0..=65535)`) is reserved for the assets
+/// created before the namespaces existed, so the community `0` can't create
+/// assets of its own.
+pub fn community_asset_id(community_id: CommunityId, index: u16) -> Option<FungibleAssetLocation> {
+	(community_id != RESERVED_ASSET_NAMESPACE)
+		.then(|| FungibleAssetLocation::Here(((community_id as u32) << 16) | index as u32))
+}
+
+/// Returns the ID of the community that owns the namespace of a given asset,
+/// if the asset belongs to the community namespaces at all.
+pub fn community_of_asset(asset_id: &FungibleAssetLocation) -> Option<CommunityId> {
+	match asset_id {
+		FungibleAssetLocation::Here(index) => {
+			Some((index >> 16) as CommunityId).filter(|id| *id != RESERVED_ASSET_NAMESPACE)
+		}
+		_ => None,
+	}
+}
+
+/// Lets the communities pallet know which community can create an asset
+/// when deciding with it, as per [`community_of_asset`].
+pub struct CommunityAssetNamespace;
+impl pallet_communities::AssetNamespace<CommunityId, FungibleAssetLocation> for CommunityAssetNamespace {
+	fn community_of_asset(asset_id: &FungibleAssetLocation) -> Option<CommunityId> {
+		community_of_asset(asset_id)
+	}
+}
+
+/// Allows the admin origin of a community to create an asset within its own
+/// namespace. The community account becomes the owner of the asset, so the
+/// community can later mint or set the metadata of the asset by dispatching
+/// the corresponding calls via [`pallet_communities::Call::dispatch_as_account`].
+pub struct EnsureCommunityAssetNamespace;
+impl EnsureOriginWithArg<RuntimeOrigin, FungibleAssetLocation> for EnsureCommunityAssetNamespace {
+	type Success = AccountId;
+
+	fn try_origin(o: RuntimeOrigin, asset_id: &FungibleAssetLocation) -> Result<Self::Success, RuntimeOrigin> {
+		let community_id = <Runtime as pallet_communities::Config>::AdminOrigin::try_origin(o.clone())?;
+		ensure!(community_of_asset(asset_id) == Some(community_id), o);
+
+		Ok(Communities::community_account(&community_id))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(asset_id: &FungibleAssetLocation) -> Result<RuntimeOrigin, ()> {
+		let community_id = community_of_asset(asset_id).ok_or(())?;
+		Ok(pallet_communities::Origin::<Runtime>::new(community_id).into())
+	}
+}
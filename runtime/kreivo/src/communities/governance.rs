@@ -4,6 +4,7 @@ use frame_system::{pallet_prelude::BlockNumberFor, EnsureRootWithSuccess};
 use pallet_communities::RuntimeOriginFor;
 use sp_std::marker::PhantomData;
 
+use pallet_communities_manager::{TrackTemplateId, DEFAULT_TRACK_TEMPLATE};
 use pallet_referenda::{BalanceOf, PalletsOriginOf, TrackIdOf, TracksInfo};
 
 pub type CommunityTracksInstance = pallet_referenda_tracks::Instance2;
@@ -66,13 +67,12 @@ where
 		o: RuntimeOriginFor<T>,
 		track_origin: &PalletsOriginOf<T>,
 	) -> Result<Self::Success, RuntimeOriginFor<T>> {
-		use fc_traits_memberships::Inspect;
 		use frame_system::RawOrigin::Signed;
 		let community_id = T::Tracks::track_for(track_origin).map_err(|_| o.clone())?;
 
 		match o.clone().into() {
 			Ok(Signed(who)) => {
-				if T::MemberMgmt::is_member_of(&community_id, &who) {
+				if pallet_communities::Pallet::<T>::is_active_member(&community_id, &who) {
 					Ok(who)
 				} else {
 					Err(o.clone())
@@ -112,6 +112,64 @@ impl pallet_referenda::Config<CommunityReferendaInstance> for Runtime {
 	type Preimages = Preimage;
 }
 
+/// The track template of a cooperative.
+pub const COOPERATIVE_TRACK_TEMPLATE: TrackTemplateId = 1;
+/// The track template of a community governed by the holders of its token.
+pub const TOKEN_HOLDERS_TRACK_TEMPLATE: TrackTemplateId = 2;
+
+type CommunityTrackInfo = pallet_referenda::TrackInfo<Balance, BlockNumber>;
+
+fn track_template(
+	name: &str,
+	max_deciding: u32,
+	decision_deposit: Balance,
+	(prepare_period, decision_period, confirm_period): (BlockNumber, BlockNumber, BlockNumber),
+	approval_floor: u32,
+	(support_ceil, support_floor): (u32, u32),
+) -> CommunityTrackInfo {
+	use pallet_referenda::Curve::LinearDecreasing;
+	use sp_runtime::Perbill;
+
+	CommunityTrackInfo {
+		name: sp_runtime::str_array(name),
+		max_deciding,
+		decision_deposit,
+		prepare_period,
+		decision_period,
+		confirm_period,
+		min_enactment_period: 1,
+		min_approval: LinearDecreasing {
+			length: Perbill::from_percent(100),
+			floor: Perbill::from_percent(approval_floor),
+			ceil: Perbill::from_percent(100),
+		},
+		min_support: LinearDecreasing {
+			length: Perbill::from_percent(100),
+			floor: Perbill::from_percent(support_floor),
+			ceil: Perbill::from_percent(support_ceil),
+		},
+	}
+}
+
+parameter_types! {
+	/// The track templates communities can be registered with. A small DAO is
+	/// the default one.
+	pub CommunityTrackTemplates: Vec<(TrackTemplateId, CommunityTrackInfo)> = vec![
+		(
+			DEFAULT_TRACK_TEMPLATE,
+			track_template("small DAO", 5, UNITS, (HOURS, 3 * DAYS, HOURS), 50, (50, 0)),
+		),
+		(
+			COOPERATIVE_TRACK_TEMPLATE,
+			track_template("cooperative", 10, UNITS, (DAYS, 7 * DAYS, DAYS), 66, (50, 10)),
+		),
+		(
+			TOKEN_HOLDERS_TRACK_TEMPLATE,
+			track_template("token-holders", 20, 10 * UNITS, (DAYS, 14 * DAYS, 2 * DAYS), 50, (30, 5)),
+		),
+	];
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 use sp_runtime::SaturatedConversion;
 
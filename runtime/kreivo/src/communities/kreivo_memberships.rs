@@ -13,6 +13,9 @@ parameter_types! {
 					<CommunityMemberships as Mutate<_, _>>::set_attribute(&group, &m, key, &value)?;
 				}
 			}
+			// Every membership that expires gets released once it's been expired for
+			// too long, no matter how it was assigned
+			Communities::track_membership_expiry(&group, &m);
 
 			Ok(())
 		});
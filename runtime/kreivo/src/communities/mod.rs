@@ -1,15 +1,25 @@
 use super::*;
 
-use frame_support::traits::TryMapSuccess;
+use core::marker::PhantomData;
+use frame_support::traits::{EnsureOrigin, Get, MapSuccess, OriginTrait, TryMapSuccess};
 #[cfg(not(feature = "runtime-benchmarks"))]
 use frame_system::EnsureNever;
 use frame_system::{EnsureRootWithSuccess, EnsureSigned};
-use pallet_communities::origin::{EnsureCommunity, EnsureSignedPays};
+use pallet_communities::{
+	origin::{EnsureCommunity, EnsureCommunityCollective, EnsureSignedPays},
+	NonFungiblesMembershipExpiry,
+};
+use pallet_communities_manager::WithoutAdminOrigin;
+use polkadot_parachain_primitives::primitives::Sibling;
 use sp_runtime::{morph_types, traits::AccountIdConversion};
 use virto_common::{CommunityId, MembershipId};
+use xcm::latest::{Junction::Parachain, Location};
+use xcm_builder::{DescribeAllTerminal, DescribeFamily, HashedDescription, SiblingParachainConvertsVia};
+use xcm_executor::traits::ConvertLocation;
 
 use fc_traits_memberships::{NonFungiblesMemberships, WithHooks};
 
+pub mod assets;
 pub mod governance;
 mod kreivo_memberships;
 pub mod memberships;
@@ -19,7 +29,7 @@ use self::{
 	governance::{CommunityReferendaInstance, CommunityTracksInstance},
 	memberships::CommunityMembershipsInstance,
 };
-use pallet_custom_origins::CreateMemberships;
+use pallet_custom_origins::{CreateMemberships, RecoverCommunityAdmin};
 
 #[cfg(feature = "runtime-benchmarks")]
 use {
@@ -45,6 +55,9 @@ parameter_types! {
 	pub const MembershipNftAttr: &'static [u8; 10] = b"membership";
 	pub const CommunityDepositAmount: Balance = UNITS / 2;
 	pub const NoPay: CreationPayment = None;
+	pub const CommunityRevenueShare: Percent = Percent::from_percent(80);
+	pub const ExpiredMembershipGracePeriod: BlockNumber = 30 * DAYS;
+	pub const MinAdminRecoveryPeriod: BlockNumber = 7 * DAYS;
 }
 
 morph_types! {
@@ -57,6 +70,47 @@ type EnsureCommunityAccount = TryMapSuccess<EnsureSigned<AccountId>, AccountToCo
 type RootCreatesCommunitiesForFree = EnsureRootWithSuccess<AccountId, NoPay>;
 type AnyoneElsePays = EnsureSignedPays<Runtime, CommunityDepositAmount, TreasuryAccount>;
 
+/// Locations within sibling chains (e.g. an organisation living on Asset Hub).
+pub struct IsSiblingLocation;
+impl Contains<Location> for IsSiblingLocation {
+	fn contains(location: &Location) -> bool {
+		matches!(location.unpack(), (1, [Parachain(_), ..]))
+	}
+}
+
+/// The account a sibling location pays the community deposit from: the
+/// sovereign account of the chain itself, or one hashed from the description of
+/// a location within it (e.g. an account or a pallet).
+pub type SiblingLocationToAccountId = (
+	SiblingParachainConvertsVia<Sibling, AccountId>,
+	HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+);
+
+/// Sibling chains registering a community via XCM pay the deposit from their
+/// sovereign account, and their location becomes the admin of the community.
+pub struct SiblingPays<Amount, Beneficiary>(PhantomData<(Amount, Beneficiary)>);
+impl<Amount, Beneficiary> EnsureOrigin<RuntimeOrigin> for SiblingPays<Amount, Beneficiary>
+where
+	Amount: Get<Balance>,
+	Beneficiary: Get<AccountId>,
+{
+	type Success = (CreationPayment, Option<OriginCaller>);
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		let location = EnsureXcm::<IsSiblingLocation>::try_origin(o.clone())?;
+		let sovereign_account = SiblingLocationToAccountId::convert_location(&location).ok_or_else(|| o.clone())?;
+		Ok((
+			Some((Amount::get(), sovereign_account, Beneficiary::get())),
+			Some(o.into_caller()),
+		))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(pallet_xcm::Origin::Xcm(Location::new(1, [Parachain(1000)])).into())
+	}
+}
+
 impl pallet_communities::Config for Runtime {
 	type CommunityId = CommunityId;
 	#[cfg(not(feature = "runtime-benchmarks"))]
@@ -68,10 +122,18 @@ impl pallet_communities::Config for Runtime {
 	type MemberMgmt =
 		WithHooks<NonFungiblesMemberships<CommunityMemberships>, kreivo_memberships::CopySystemAttributesOnAssign>;
 	type MembershipId = MembershipId;
+	type MembershipExpiry = NonFungiblesMembershipExpiry<CommunityMemberships, AccountId>;
+	type ExpiredMembershipGracePeriod = ExpiredMembershipGracePeriod;
+	type OnMembershipReleased = CommunitiesManager;
+	type RecoveryOrigin = EitherOf<EnsureRoot<AccountId>, RecoverCommunityAdmin>;
+	// Only a passed referendum vetoes, as admins can act as the community account via `dispatch_as_account`
+	type AdminRecoveryVetoOrigin = EnsureCommunityCollective<Self>;
+	type MinAdminRecoveryPeriod = MinAdminRecoveryPeriod;
 
 	type Polls = CommunityReferenda;
 
 	type Assets = Assets;
+	type AssetNamespace = assets::CommunityAssetNamespace;
 	type AssetsFreezer = AssetsFreezer;
 	type Balances = Balances;
 
@@ -94,14 +156,24 @@ impl pallet_communities_manager::Config for Runtime {
 	type CreateCollection = CommunityMemberships;
 	type Tracks = CommunityTracks;
 	type RankedCollective = KreivoCollective;
-	type RegisterOrigin = EitherOf<RootCreatesCommunitiesForFree, AnyoneElsePays>;
+	type RegisterOrigin = EitherOf<
+		MapSuccess<
+			EitherOf<RootCreatesCommunitiesForFree, AnyoneElsePays>,
+			WithoutAdminOrigin<CreationPayment, OriginCaller>,
+		>,
+		SiblingPays<CommunityDepositAmount, TreasuryAccount>,
+	>;
 
 	type CreateMembershipsOrigin = EitherOf<EnsureRoot<AccountId>, CreateMemberships>;
+	type TrackTemplatesOrigin = EnsureRoot<AccountId>;
+	type InitialTrackTemplates = governance::CommunityTrackTemplates;
 	type MembershipId = MembershipId;
 	type MembershipsManagerOwner = TreasuryAccount;
 	type MembershipsManagerCollectionId = MembershipsCollectionId;
 	type CreateMemberships = CommunityMemberships;
 	type MakeTank = MembershipsGasTank;
+	type CommunityRevenueShare = CommunityRevenueShare;
+	type GasToFee = WeightToFee;
 
 	type WeightInfo = crate::weights::pallet_communities_manager::WeightInfo<Self>;
 }
@@ -119,7 +191,7 @@ impl BenchmarkHelper<Runtime> for CommunityBenchmarkHelper {
 		1
 	}
 	fn community_asset_id() -> AssetIdOf<Runtime> {
-		1u32.into()
+		assets::community_asset_id(Self::community_id(), 1).expect("the community has an asset namespace")
 	}
 	fn community_desired_size() -> u32 {
 		u8::MAX.into()
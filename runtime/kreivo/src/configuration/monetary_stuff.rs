@@ -1,6 +1,6 @@
 use super::*;
 
-use fc_traits_gas_tank::{NonFungibleGasTank, SelectNonFungibleItem};
+use fc_traits_gas_tank::{GasBurner, NonFungibleGasTank, SelectNonFungibleItem};
 use pallet_asset_tx_payment::FungiblesAdapter;
 use pallet_assets::BalanceToAssetBalance;
 use pallet_transaction_payment::FungibleAdapter;
@@ -8,7 +8,7 @@ use runtime_common::impls::AssetsToBlockAuthor;
 use virto_common::MembershipId;
 
 #[cfg(not(feature = "runtime-benchmarks"))]
-use frame_support::traits::{AsEnsureOriginWithArg, NeverEnsureOrigin};
+use crate::communities::assets::EnsureCommunityAssetNamespace;
 
 #[cfg(feature = "runtime-benchmarks")]
 use frame_system::EnsureSigned;
@@ -84,9 +84,10 @@ impl pallet_assets::Config<KreivoAssetsInstance> for Runtime {
 	type AssetId = FungibleAssetLocation;
 	type AssetIdParameter = FungibleAssetLocation;
 	type Currency = Balances;
-	/// Only root can create assets and force state changes.
+	/// Communities can create assets within their own namespace. Only root can
+	/// force state changes.
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+	type CreateOrigin = EnsureCommunityAssetNamespace;
 	#[cfg(feature = "runtime-benchmarks")]
 	type CreateOrigin = EnsureSigned<AccountId>;
 	type ForceOrigin = AssetsForceOrigin;
@@ -151,18 +152,95 @@ parameter_types! {
 		Box::new(|community, membership| {
 			use frame_support::traits::nonfungibles_v2::Inspect;
 			let membership_expiration = b"membership_expiration";
-			CommunityMemberships::typed_system_attribute(&community, Some(&membership), &membership_expiration)
-				// If there's an expiration date, check it against block number
-				.map(|expiration| System::block_number() <= expiration)
-				// Otherwise, the membership will not expire
-				.unwrap_or(true)
+			let is_not_expired =
+				CommunityMemberships::typed_system_attribute(&community, Some(&membership), &membership_expiration)
+					// If there's an expiration date, check it against block number
+					.map(|expiration| System::block_number() <= expiration)
+					// Otherwise, the membership will not expire
+					.unwrap_or(true);
+			if is_not_expired {
+				CommunitiesPayForGas::note_selected_tank(community, membership);
+			}
+			is_not_expired
 		});
 }
 
 pub type MembershipsGasTank =
 	NonFungibleGasTank<Runtime, CommunityMemberships, pallet_nfts::ItemConfig, MembershipIsNotExpired>;
 
+/// The last membership selected while burning gas, which is the one the gas
+/// is burned from, along with its tank before burning it.
+#[frame_support::storage_alias]
+type SelectedGasTank = StorageValue<CommunitiesGasTanks, Option<(CommunityId, MembershipId, Option<Vec<u8>>)>>;
+
+/// Burns gas from the tanks of the memberships of an account, charging the
+/// fee for the weight actually used to whoever filled the tank the gas was
+/// taken from. Tanks sponsored by the treasury aren't charged to anyone.
+pub struct CommunitiesPayForGas;
+
+impl CommunitiesPayForGas {
+	fn tank_of(community: &CommunityId, membership: &MembershipId) -> Option<Vec<u8>> {
+		use frame_support::traits::nonfungibles_v2::Inspect;
+		CommunityMemberships::system_attribute(community, Some(membership), b"membership_gas")
+	}
+
+	fn note_selected_tank(community: CommunityId, membership: MembershipId) {
+		// Only recorded while burning gas
+		if SelectedGasTank::exists() {
+			let tank = Self::tank_of(&community, &membership);
+			SelectedGasTank::put(Some((community, membership, tank)));
+		}
+	}
+}
+
+impl GasBurner for CommunitiesPayForGas {
+	type AccountId = AccountId;
+	type Gas = Weight;
+
+	fn check_available_gas(who: &AccountId, estimated: &Weight) -> Option<Weight> {
+		MembershipsGasTank::check_available_gas(who, estimated)
+	}
+
+	fn burn_gas(who: &AccountId, expected: &Weight, used: &Weight) -> Weight {
+		use frame_support::traits::fungible::Mutate;
+
+		SelectedGasTank::put(None::<(CommunityId, MembershipId, Option<Vec<u8>>)>);
+		let remaining = MembershipsGasTank::burn_gas(who, expected, used);
+		let Some(Some((community, membership, tank))) = SelectedGasTank::take() else {
+			return remaining;
+		};
+		if Self::tank_of(&community, &membership) == tank {
+			return remaining;
+		}
+		let Some(payer) = pallet_communities_manager::GasTankPayers::<Runtime>::get(membership, community) else {
+			return remaining;
+		};
+
+		let fee = <WeightToFee as frame_support::weights::WeightToFee>::weight_to_fee(used);
+		if Balances::transfer(
+			&payer,
+			&TreasuryAccount::get(),
+			fee,
+			frame_support::traits::tokens::Preservation::Preserve,
+		)
+		.is_err()
+		{
+			// The payer can no longer afford the gas, so the tank is emptied
+			// instead of burning more gas nobody pays for
+			let _ = <CommunityMemberships as frame_support::traits::nonfungibles_v2::Mutate<_, _>>::clear_attribute(
+				&community,
+				&membership,
+				b"membership_gas",
+			);
+			pallet_communities_manager::GasTankPayers::<Runtime>::remove(membership, community);
+			return Weight::zero();
+		}
+
+		remaining
+	}
+}
+
 impl pallet_gas_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type GasBurner = MembershipsGasTank;
+	type GasBurner = CommunitiesPayForGas;
 }
@@ -37,6 +37,8 @@ pub mod pallet_custom_origins {
 		ReferendumCanceller,
 		/// Origin able to kill referenda.
 		ReferendumKiller,
+		/// Origin able to recover the admin origin of a community.
+		RecoverCommunityAdmin,
 	}
 
 	macro_rules! decl_unit_ensures {
@@ -70,5 +72,10 @@ pub mod pallet_custom_origins {
 		() => {}
 	}
 
-	decl_unit_ensures!(CreateMemberships, ReferendumCanceller, ReferendumKiller);
+	decl_unit_ensures!(
+		CreateMemberships,
+		ReferendumCanceller,
+		ReferendumKiller,
+		RecoverCommunityAdmin
+	);
 }
@@ -151,9 +151,22 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_communities_manager::migrations::v1::SeedTrackTemplates<Runtime>,
+	pallet_payments::migrations::v1::AddDiscounts<Runtime>,
+	pallet_payments::migrations::v2::IndexByBeneficiary<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	Migrations,
+>;
 
 impl_opaque_keys! {
 	pub struct SessionKeys {
@@ -1,29 +1,64 @@
 use super::*;
 
-use frame_support::traits::EitherOf;
+use frame_support::traits::{fungible::UnionOf, EitherOf, EnsureOrigin, EnsureOriginWithArg};
 use frame_system::EnsureSigned;
-use pallet_communities::origin::AsSignedByCommunity;
-use parity_scale_codec::Encode;
-use sp_runtime::traits::AccountIdConversion;
+use pallet_communities::origin::{AsSignedByCommunity, EnsureCommunity};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Convert},
+	Either,
+};
 
 parameter_types! {
 	pub const MaxRemarkLength: u8 = 50;
 	pub const IncentivePercentage: Percent = Percent::from_percent(INCENTIVE_PERCENTAGE);
 	pub const PaymentPalletId: PalletId = PalletId(*b"payments");
+	pub const PaymentDepositBase: Balance = deposit(2, 0);
+	pub const PaymentDepositPerByte: Balance = deposit(0, 1);
+	pub const PaymentDepositHoldReason: RuntimeHoldReason = RuntimeHoldReason::Payments(pallet_payments::HoldReason::PaymentDeposit);
+	pub const DefaultDisputeResult: DisputeResult = DisputeResult {
+		percent_beneficiary: Percent::from_percent(50),
+		in_favor_of: Role::Sender,
+	};
 }
 
+/// The native balance is the token of the relay chain, so payments made with
+/// its location go through `Balances` and the rest through `Assets`. Asset ids
+/// are kept as they are, so payments stored before the native balance could be
+/// used decode the same.
+pub struct NativeFromRelayLocation;
+impl Convert<FungibleAssetLocation, Either<(), FungibleAssetLocation>> for NativeFromRelayLocation {
+	fn convert(location: FungibleAssetLocation) -> Either<(), FungibleAssetLocation> {
+		match location {
+			FungibleAssetLocation::External { network, child: None }
+				if Some(network.into()) == crate::xcm_config::RelayNetwork::get() =>
+			{
+				Either::Left(())
+			}
+			_ => Either::Right(location),
+		}
+	}
+}
+
+/// Payments can be made in the native balance as well as in any asset
+pub type PaymentAssets = UnionOf<Balances, Assets, NativeFromRelayLocation, FungibleAssetLocation, AccountId>;
+
 #[cfg(feature = "runtime-benchmarks")]
 pub struct PaymentsBenchmarkHelper;
 #[cfg(feature = "runtime-benchmarks")]
 impl pallet_payments::BenchmarkHelper<AccountId, FungibleAssetLocation, Balance> for PaymentsBenchmarkHelper {
 	fn create_asset(id: FungibleAssetLocation, admin: AccountId, is_sufficient: bool, min_balance: Balance) {
-		<Assets as frame_support::traits::tokens::fungibles::Create<AccountId>>::create(
-			id,
-			admin,
-			is_sufficient,
-			min_balance,
-		)
-		.unwrap();
+		// The native balance needs no creation
+		if let Either::Right(id) = NativeFromRelayLocation::convert(id) {
+			<Assets as frame_support::traits::tokens::fungibles::Create<AccountId>>::create(
+				id,
+				admin,
+				is_sufficient,
+				min_balance,
+			)
+			.unwrap();
+		}
 	}
 }
 
@@ -34,6 +69,19 @@ pub const SENDER_FEE: Percent = Percent::from_percent(1);
 pub const BENEFICIARY_FEE: Percent = Percent::from_percent(3);
 pub const INCENTIVE_PERCENTAGE: u8 = 10;
 
+impl KreivoFeeHandler {
+	fn fee(asset: &AssetIdOf<Runtime>, amount: &Balance, fee: Percent) -> Balance {
+		let min = <PaymentAssets as fungibles::Inspect<AccountId>>::minimum_balance(asset.clone());
+		min.max(fee.mul_floor(*amount))
+	}
+
+	fn community_of(who: &AccountId) -> Option<CommunityId> {
+		let pallet_id = crate::communities::CommunityPalletId::get();
+		PalletId::try_from_sub_account::<CommunityId>(who)
+			.and_then(|(pid, community_id)| (pid == pallet_id).then_some(community_id))
+	}
+}
+
 impl FeeHandler<Runtime> for KreivoFeeHandler {
 	fn apply_fees(
 		asset: &AssetIdOf<Runtime>,
@@ -42,19 +90,15 @@ impl FeeHandler<Runtime> for KreivoFeeHandler {
 		amount: &Balance,
 		_remark: Option<&[u8]>,
 	) -> Fees<Runtime> {
-		let min = <Assets as fungibles::Inspect<AccountId>>::minimum_balance(*asset);
-		let pallet_id = crate::communities::CommunityPalletId::get();
-		let default_fee = |fee: Percent| (TreasuryAccount::get(), min.max(fee.mul_floor(*amount)), MANDATORY_FEE);
-		let is_community =
-			|who| matches!(PalletId::try_from_sub_account::<CommunityId>(who), Some((pid, _)) if pallet_id == pid );
+		let default_fee = |fee: Percent| (TreasuryAccount::get(), Self::fee(asset, amount, fee), MANDATORY_FEE);
 
 		let mut sender_fees = vec![];
 		let mut beneficiary_fees = vec![];
 
-		if !is_community(sender) {
+		if Self::community_of(sender).is_none() {
 			sender_fees.push(default_fee(SENDER_FEE))
 		}
-		if !is_community(beneficiary) {
+		if Self::community_of(beneficiary).is_none() {
 			beneficiary_fees.push(default_fee(BENEFICIARY_FEE))
 		}
 		Fees {
@@ -62,19 +106,104 @@ impl FeeHandler<Runtime> for KreivoFeeHandler {
 			beneficiary_pays: BoundedVec::try_from(beneficiary_fees).unwrap(),
 		}
 	}
+
+	/// Payments between a community and its own members are exempt from the
+	/// fee the member would otherwise pay to the treasury, as long as their
+	/// membership hasn't expired.
+	fn apply_discounts(
+		asset: &AssetIdOf<Runtime>,
+		sender: &AccountId,
+		beneficiary: &AccountId,
+		amount: &Balance,
+		_remark: Option<&[u8]>,
+	) -> DiscountsOf<Runtime> {
+		let member_discount = |role: Role, fee: Percent| Discount {
+			target: DiscountTarget::Fee(role, TreasuryAccount::get()),
+			amount: Self::fee(asset, amount, fee),
+		};
+
+		let mut discounts = vec![];
+		if Self::community_of(beneficiary)
+			.is_some_and(|community_id| Communities::is_active_member(&community_id, sender))
+		{
+			discounts.push(member_discount(Role::Sender, SENDER_FEE));
+		}
+		if Self::community_of(sender)
+			.is_some_and(|community_id| Communities::is_active_member(&community_id, beneficiary))
+		{
+			discounts.push(member_discount(Role::Beneficiary, BENEFICIARY_FEE));
+		}
+		BoundedVec::truncate_from(discounts)
+	}
+}
+
+/// Who judges the disputes of a payment, as named by its creator
+#[derive(Clone, Debug, Decode, Encode, Eq, MaxEncodedLen, PartialEq, TypeInfo)]
+pub enum PaymentArbiter {
+	/// A community (e.g. a marketplace), judging through its own governance
+	Community(CommunityId),
+	/// A specific account
+	Account(AccountId),
+}
+
+/// Ensures the origin is the arbiter of a payment. The incentive for resolving
+/// the dispute is paid to the account of the arbiter.
+pub struct EnsurePaymentArbiter;
+impl EnsureOriginWithArg<RuntimeOrigin, PaymentArbiter> for EnsurePaymentArbiter {
+	type Success = AccountId;
+
+	fn try_origin(o: RuntimeOrigin, arbiter: &PaymentArbiter) -> Result<Self::Success, RuntimeOrigin> {
+		match arbiter {
+			PaymentArbiter::Community(community_id) => {
+				let id = EnsureCommunity::<Runtime>::try_origin(o.clone())?;
+				ensure!(id == *community_id, o);
+				Ok(Communities::community_account(community_id))
+			}
+			PaymentArbiter::Account(account) => {
+				let who = EnsureSigned::<AccountId>::try_origin(o.clone())?;
+				ensure!(who == *account, o);
+				Ok(who)
+			}
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(arbiter: &PaymentArbiter) -> Result<RuntimeOrigin, ()> {
+		Ok(match arbiter {
+			PaymentArbiter::Community(community_id) => pallet_communities::Origin::<Runtime>::new(*community_id).into(),
+			PaymentArbiter::Account(account) => RuntimeOrigin::signed(account.clone()),
+		})
+	}
+}
+
+/// The account of an arbiter, which is the account of the community when a
+/// community judges the dispute
+pub struct PaymentArbiterAccount;
+impl Convert<PaymentArbiter, AccountId> for PaymentArbiterAccount {
+	fn convert(arbiter: PaymentArbiter) -> AccountId {
+		match arbiter {
+			PaymentArbiter::Community(community_id) => Communities::community_account(&community_id),
+			PaymentArbiter::Account(account) => account,
+		}
+	}
 }
 
 impl pallet_payments::PaymentId<Runtime> for virto_common::PaymentId {
-	fn next(_: &AccountId, beneficiary: &AccountId) -> Option<Self> {
+	fn next(_: &AccountId, _: &AccountId) -> Option<Self> {
 		let block: u32 = System::block_number();
-		let idx = System::extrinsic_index()?;
-		Some((block, idx, beneficiary.encode().as_slice()).into())
+		// An extrinsic index that doesn't fit in the id fails the payment
+		// rather than risking a collision
+		let idx = u16::try_from(System::extrinsic_index()?).ok()?;
+		// Payments created in the same extrinsic (e.g. in a batch) are told apart
+		// by the index of the payment within the block
+		let payment_index = pallet_payments::Pallet::<Runtime>::next_payment_index()?;
+		Some((block, idx, payment_index).into())
 	}
 }
 
 impl pallet_payments::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Assets = Assets;
+	type Assets = PaymentAssets;
 	type AssetsBalance = Balance;
 	type PaymentId = virto_common::PaymentId;
 	type FeeHandler = KreivoFeeHandler;
@@ -83,14 +212,30 @@ impl pallet_payments::Config for Runtime {
 	type SenderOrigin = EitherOf<AsSignedByCommunity<Self>, EnsureSigned<AccountId>>;
 	type BeneficiaryOrigin = EnsureSigned<AccountId>;
 	type DisputeResolver = frame_system::EnsureRootWithSuccess<AccountId, TreasuryAccount>;
+	type DefaultDisputeResult = DefaultDisputeResult;
+	type Arbiter = PaymentArbiter;
+	type ArbiterOrigin = EnsurePaymentArbiter;
+	type ArbiterAccount = PaymentArbiterAccount;
 	type PalletId = PaymentPalletId;
 	type RuntimeHoldReason = RuntimeHoldReason;
+	type Consideration = HoldConsideration<
+		AccountId,
+		Balances,
+		PaymentDepositHoldReason,
+		LinearStoragePrice<PaymentDepositBase, PaymentDepositPerByte, Balance>,
+	>;
 	type MaxDiscounts = ConstU32<10>;
+	type MaxMilestones = ConstU32<20>;
+	type MaxSplits = ConstU32<10>;
+	type MaxEvidence = ConstU32<20>;
 	type MaxFees = ConstU32<50>;
 	type RuntimeCall = RuntimeCall;
 	type Scheduler = Scheduler;
 	type Preimages = Preimage;
 	type CancelBufferBlockLength = ConstU32<14400>; // 2 days
+	type DisputeReviewPeriod = ConstU32<100800>; // 14 days
+	type PaymentRequestLifetime = ConstU32<216000>; // 30 days
+	type FinishedPaymentRetention = ConstU32<50400>; // 7 days
 	type PalletsOrigin = OriginCaller;
 	type WeightInfo = crate::weights::pallet_payments::WeightInfo<Runtime>;
 	#[cfg(feature = "runtime-benchmarks")]
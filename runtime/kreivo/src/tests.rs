@@ -75,3 +75,581 @@ fn runtime_sanity_call_does_not_exceed_1kb() {
 	// Payments: pallet_payments = 60
 	assert_call_size!(pallet_payments);
 }
+
+#[test]
+fn payment_ids_are_unique_within_an_extrinsic() {
+	use crate::{AccountId, System};
+	use virto_common::PaymentId;
+
+	let next_id = |sender: &AccountId, beneficiary: &AccountId| {
+		<PaymentId as pallet_payments::PaymentId<Runtime>>::next(sender, beneficiary).unwrap()
+	};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+		System::set_extrinsic_index(2);
+
+		// Two payments in a batch, to beneficiaries sharing their first bytes
+		let sender = AccountId::new([0; 32]);
+		let beneficiary = AccountId::new([1; 32]);
+		let mut other_beneficiary = [1; 32];
+		other_beneficiary[31] = 2;
+		let other_beneficiary = AccountId::new(other_beneficiary);
+
+		let id = next_id(&sender, &beneficiary);
+		let second_id = next_id(&sender, &beneficiary);
+		let third_id = next_id(&sender, &other_beneficiary);
+		assert_ne!(id, second_id);
+		assert_ne!(second_id, third_id);
+		assert_ne!(id, third_id);
+
+		assert_eq!(id.block_number(), 1);
+		assert_eq!(id.extrinsic_index(), 2);
+	});
+}
+
+#[test]
+fn payments_in_a_batch_get_different_ids() {
+	use crate::{AccountId, Balances, RuntimeCall, RuntimeOrigin, System, UNITS};
+	use frame_support::{assert_ok, traits::fungible::Mutate};
+	use sp_runtime::traits::{Dispatchable, StaticLookup};
+	use virto_common::{FungibleAssetLocation, NetworkId};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+		System::set_extrinsic_index(1);
+
+		let sender = AccountId::new([0; 32]);
+		let beneficiary = AccountId::new([1; 32]);
+		assert_ok!(Balances::mint_into(&sender, 1_000 * UNITS));
+		assert_ok!(Balances::mint_into(&beneficiary, UNITS));
+
+		let pay = RuntimeCall::Payments(pallet_payments::Call::pay {
+			beneficiary: <Runtime as frame_system::Config>::Lookup::unlookup(beneficiary),
+			asset: FungibleAssetLocation::External {
+				network: NetworkId::Kusama,
+				child: None,
+			},
+			amount: 10 * UNITS,
+			remark: None,
+			auto_release_after: None,
+			arbiter: None,
+		});
+		let batch = RuntimeCall::Utility(pallet_utility::Call::batch_all {
+			calls: vec![pay.clone(), pay],
+		});
+		assert_ok!(batch.dispatch(RuntimeOrigin::signed(sender)));
+
+		let mut ids = pallet_payments::PaymentParties::<Runtime>::iter_keys().collect::<Vec<_>>();
+		ids.sort_by_key(|id| id.payment_index());
+		assert_eq!(ids.len(), 2);
+		assert!(ids.iter().all(|id| id.block_number() == 1 && id.extrinsic_index() == 1));
+		assert_eq!(ids[0].payment_index(), 0);
+		assert_eq!(ids[1].payment_index(), 1);
+	});
+}
+
+#[test]
+fn community_assets_are_namespaced() {
+	use crate::communities::assets::{community_asset_id, community_of_asset};
+	use virto_common::FungibleAssetLocation;
+
+	let asset_id = community_asset_id(1, 7).unwrap();
+	assert_eq!(asset_id, FungibleAssetLocation::Here(65_543));
+	assert_eq!(community_of_asset(&asset_id), Some(1));
+	assert_ne!(community_of_asset(&community_asset_id(2, 7).unwrap()), Some(1));
+
+	// Assets created before the namespaces belong to no community
+	assert_eq!(community_asset_id(0, 7), None);
+	assert_eq!(community_of_asset(&FungibleAssetLocation::Here(7)), None);
+}
+
+#[cfg(not(feature = "runtime-benchmarks"))]
+#[test]
+fn communities_create_assets_only_within_their_namespace() {
+	use crate::{communities::assets::community_asset_id, AccountId, Assets, Balances, Communities, RuntimeOrigin};
+	use frame_support::{assert_noop, assert_ok, traits::fungible::Mutate};
+	use sp_runtime::{traits::StaticLookup, DispatchError};
+	use virto_common::FungibleAssetLocation;
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		let community_account = Communities::community_account(&1);
+		assert_ok!(Balances::mint_into(&community_account, 1_000 * crate::UNITS));
+		let admin = <Runtime as frame_system::Config>::Lookup::unlookup(community_account.clone());
+
+		assert_ok!(Assets::create(
+			RuntimeOrigin::signed(community_account.clone()),
+			community_asset_id(1, 7).unwrap(),
+			admin.clone(),
+			1,
+		));
+		assert_noop!(
+			Assets::create(
+				RuntimeOrigin::signed(community_account.clone()),
+				community_asset_id(2, 7).unwrap(),
+				admin.clone(),
+				1,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Assets::create(
+				RuntimeOrigin::signed(community_account),
+				FungibleAssetLocation::Here(7),
+				admin,
+				1,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Assets::create(
+				RuntimeOrigin::signed(AccountId::new([0; 32])),
+				community_asset_id(1, 8).unwrap(),
+				<Runtime as frame_system::Config>::Lookup::unlookup(AccountId::new([0; 32])),
+				1,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn payments_can_be_made_in_native_balance() {
+	use crate::{AccountId, Balances, Payments, RuntimeHoldReason, RuntimeOrigin, System, UNITS};
+	use frame_support::{
+		assert_ok,
+		traits::fungible::{Inspect, InspectHold, Mutate},
+	};
+	use sp_runtime::traits::StaticLookup;
+	use virto_common::{FungibleAssetLocation, NetworkId};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		System::set_block_number(1);
+		System::set_extrinsic_index(1);
+
+		let sender = AccountId::new([0; 32]);
+		let beneficiary = AccountId::new([1; 32]);
+		assert_ok!(Balances::mint_into(&sender, 1_000 * UNITS));
+		assert_ok!(Balances::mint_into(&beneficiary, UNITS));
+
+		assert_ok!(Payments::pay(
+			RuntimeOrigin::signed(sender.clone()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(beneficiary.clone()),
+			// The token of the relay chain is the native balance
+			FungibleAssetLocation::External {
+				network: NetworkId::Kusama,
+				child: None,
+			},
+			100 * UNITS,
+			None,
+			None,
+			None,
+		));
+
+		let reason = RuntimeHoldReason::Payments(pallet_payments::HoldReason::TransferPayment);
+		assert_eq!(Balances::balance_on_hold(&reason, &beneficiary), 100 * UNITS);
+
+		let payment_id = pallet_payments::PaymentParties::<Runtime>::iter_keys()
+			.next()
+			.expect("payment was created; qed");
+		assert_ok!(Payments::release(RuntimeOrigin::signed(sender), payment_id));
+
+		// The beneficiary gets the amount, minus its fee (3%)
+		assert_eq!(Balances::balance_on_hold(&reason, &beneficiary), 0);
+		assert_eq!(Balances::balance(&beneficiary), UNITS + 97 * UNITS);
+	});
+}
+
+#[test]
+fn community_track_templates_are_seeded_at_genesis() {
+	use crate::communities::governance::{COOPERATIVE_TRACK_TEMPLATE, TOKEN_HOLDERS_TRACK_TEMPLATE};
+	use frame_support::traits::BuildGenesisConfig;
+	use pallet_communities_manager::{TrackTemplates, DEFAULT_TRACK_TEMPLATE};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		// Panics if any of the templates is not valid
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		for id in [
+			DEFAULT_TRACK_TEMPLATE,
+			COOPERATIVE_TRACK_TEMPLATE,
+			TOKEN_HOLDERS_TRACK_TEMPLATE,
+		] {
+			assert!(TrackTemplates::<Runtime>::contains_key(id));
+		}
+	});
+}
+
+#[test]
+fn sibling_locations_register_communities_via_xcm_transact() {
+	use crate::{
+		communities::SiblingLocationToAccountId,
+		xcm_config::{LocationToAccountId, XcmOriginToTransactDispatchOrigin},
+		AccountId, Balances, Communities, CommunitiesManager, RuntimeOrigin, UNITS,
+	};
+	use frame_support::{
+		assert_ok,
+		traits::fungible::{Inspect, Mutate},
+		BoundedVec,
+	};
+	use sp_runtime::traits::StaticLookup;
+	use xcm::latest::prelude::*;
+	use xcm_executor::traits::{ConvertLocation, ConvertOrigin};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		// An organisation living on a sibling chain
+		let location = Location::new(
+			1,
+			[
+				Parachain(1000),
+				AccountId32 {
+					network: None,
+					id: [7; 32],
+				},
+			],
+		);
+		let sovereign_account = SiblingLocationToAccountId::convert_location(&location)
+			.expect("sibling sub-locations have an account; qed");
+		// Only the community deposit is paid from it, the rest of XCM doesn't see it
+		assert_eq!(LocationToAccountId::convert_location(&location), None);
+		assert_ok!(Balances::mint_into(&sovereign_account, 1_000 * UNITS));
+
+		// The origin a `Transact` with `OriginKind::Xcm` dispatches calls with
+		let origin: RuntimeOrigin =
+			<XcmOriginToTransactDispatchOrigin as ConvertOrigin<RuntimeOrigin>>::convert_origin(
+				location,
+				OriginKind::Xcm,
+			)
+			.expect("xcm origins pass through; qed");
+
+		assert_ok!(CommunitiesManager::register(
+			origin,
+			1,
+			BoundedVec::truncate_from(b"Sibling Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(AccountId::new([1; 32])),
+			None,
+			None,
+			Default::default(),
+		));
+		assert!(Communities::community_exists(&1));
+		assert!(Balances::balance(&sovereign_account) < 1_000 * UNITS);
+	});
+}
+
+#[test]
+fn community_members_pay_their_community_without_fees() {
+	use crate::{
+		payments::{KreivoFeeHandler, SENDER_FEE},
+		AccountId, Balances, Communities, CommunitiesManager, CommunityMemberships, RuntimeOrigin, TreasuryAccount,
+		UNITS,
+	};
+	use frame_support::{
+		assert_ok,
+		traits::{fungible::Mutate, nonfungibles_v2::Mutate as _},
+		BoundedVec,
+	};
+	use pallet_payments::{Discount, DiscountTarget, FeeHandler, Role};
+	use sp_runtime::traits::StaticLookup;
+	use virto_common::{FungibleAssetLocation, NetworkId};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		let member = AccountId::new([1; 32]);
+		let outsider = AccountId::new([2; 32]);
+		assert_ok!(Balances::mint_into(&member, 1_000 * UNITS));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(member.clone()),
+			None,
+			None,
+			Default::default(),
+		));
+		assert_ok!(CommunityMemberships::mint_into(
+			&1,
+			&1,
+			&member,
+			&pallet_nfts::ItemConfig::default(),
+			true
+		));
+		assert!(Communities::is_member(&1, &member));
+
+		let community_account = Communities::community_account(&1);
+		let amount = 100 * UNITS;
+		let native = FungibleAssetLocation::External {
+			network: NetworkId::Kusama,
+			child: None,
+		};
+		let discounts_of = |sender: &AccountId, beneficiary: &AccountId| {
+			KreivoFeeHandler::apply_discounts(&native, sender, beneficiary, &amount, None).into_inner()
+		};
+
+		assert_eq!(
+			discounts_of(&member, &community_account),
+			vec![Discount {
+				target: DiscountTarget::Fee(Role::Sender, TreasuryAccount::get()),
+				amount: SENDER_FEE.mul_floor(amount),
+			}]
+		);
+		assert_eq!(discounts_of(&outsider, &community_account), vec![]);
+		assert_eq!(discounts_of(&member, &outsider), vec![]);
+	});
+}
+
+#[test]
+fn members_with_expired_memberships_pay_fees_to_their_community() {
+	use crate::{
+		payments::KreivoFeeHandler, AccountId, BlockNumber, Communities, CommunitiesManager, CommunityMemberships,
+		RuntimeOrigin, System, UNITS,
+	};
+	use frame_support::{assert_ok, traits::nonfungibles_v2::Mutate, BoundedVec};
+	use pallet_payments::FeeHandler;
+	use sp_runtime::traits::StaticLookup;
+	use virto_common::{FungibleAssetLocation, NetworkId};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		let member = AccountId::new([1; 32]);
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(member.clone()),
+			None,
+			None,
+			Default::default(),
+		));
+		assert_ok!(CommunityMemberships::mint_into(
+			&1,
+			&1,
+			&member,
+			&pallet_nfts::ItemConfig::default(),
+			true
+		));
+		assert_ok!(CommunityMemberships::set_typed_attribute(
+			&1,
+			&1,
+			&b"membership_expiration",
+			&(10 as BlockNumber)
+		));
+		System::set_block_number(11);
+		assert!(Communities::is_member(&1, &member));
+		assert!(!Communities::is_active_member(&1, &member));
+
+		let community_account = Communities::community_account(&1);
+		let native = FungibleAssetLocation::External {
+			network: NetworkId::Kusama,
+			child: None,
+		};
+		for (sender, beneficiary) in [(&member, &community_account), (&community_account, &member)] {
+			assert!(KreivoFeeHandler::apply_discounts(&native, sender, beneficiary, &(100 * UNITS), None).is_empty());
+		}
+	});
+}
+
+#[test]
+fn the_relay_location_is_the_native_balance_of_payments() {
+	use crate::payments::NativeFromRelayLocation;
+	use sp_runtime::{traits::Convert, Either};
+	use virto_common::{FungibleAssetLocation, NetworkId};
+
+	let relay = FungibleAssetLocation::External {
+		network: NetworkId::Kusama,
+		child: None,
+	};
+	assert_eq!(NativeFromRelayLocation::convert(relay), Either::Left(()));
+
+	for asset in [
+		FungibleAssetLocation::Here(1),
+		FungibleAssetLocation::External {
+			network: NetworkId::Polkadot,
+			child: None,
+		},
+	] {
+		assert_eq!(NativeFromRelayLocation::convert(asset), Either::Right(asset));
+	}
+}
+
+#[test]
+fn community_admins_can_not_veto_the_recovery_of_their_origin() {
+	use crate::{
+		AccountId, Balances, Communities, CommunitiesManager, OriginCaller, RuntimeCall, RuntimeOrigin, DAYS, UNITS,
+	};
+	use frame_support::{assert_noop, assert_ok, traits::fungible::Mutate, BoundedVec};
+	use sp_runtime::{traits::StaticLookup, DispatchError};
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		let admin = AccountId::new([1; 32]);
+		let new_admin = AccountId::new([2; 32]);
+		assert_ok!(Balances::mint_into(&admin, 1_000 * UNITS));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(admin.clone()),
+			None,
+			None,
+			Default::default(),
+		));
+		assert_ok!(Communities::set_admin_recovery_period(
+			RuntimeOrigin::signed(admin.clone()),
+			7 * DAYS,
+		));
+		let lost_admin: OriginCaller = frame_system::RawOrigin::Signed(admin.clone()).into();
+		let new_admin: OriginCaller = frame_system::RawOrigin::Signed(new_admin).into();
+		assert_ok!(Communities::propose_admin_recovery(
+			RuntimeOrigin::root(),
+			1,
+			lost_admin,
+			new_admin,
+		));
+
+		assert_noop!(
+			Communities::veto_admin_recovery(RuntimeOrigin::signed(admin.clone())),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Communities::dispatch_as_account(
+				RuntimeOrigin::signed(admin),
+				Box::new(RuntimeCall::Communities(
+					pallet_communities::Call::veto_admin_recovery {}
+				)),
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn communities_pay_only_for_the_gas_of_the_tanks_they_fill() {
+	use crate::{
+		configuration::monetary_stuff::CommunitiesPayForGas, AccountId, Balances, Communities, CommunitiesManager,
+		CommunityMemberships, MembershipsGasTank, RuntimeOrigin, TreasuryAccount, WeightToFee, UNITS,
+	};
+	use fc_traits_gas_tank::{GasBurner, MakeTank};
+	use frame_support::{
+		assert_ok,
+		traits::{fungible::Mutate, nonfungibles_v2::Mutate as _},
+		weights::{Weight, WeightToFee as _},
+		BoundedVec,
+	};
+	use sp_runtime::traits::StaticLookup;
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		let admin = AccountId::new([1; 32]);
+		let member = AccountId::new([2; 32]);
+		assert_ok!(Balances::mint_into(&admin, 1_000 * UNITS));
+		assert_ok!(Balances::mint_into(&TreasuryAccount::get(), 1_000 * UNITS));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(admin.clone()),
+			None,
+			None,
+			Default::default(),
+		));
+		assert_ok!(CommunityMemberships::mint_into(
+			&1,
+			&1,
+			&member,
+			&pallet_nfts::ItemConfig::default(),
+			true
+		));
+
+		let community_account = Communities::community_account(&1);
+		assert_ok!(Balances::mint_into(&community_account, 1_000 * UNITS));
+		let capacity = Weight::from_parts(1_000_000_000, 0);
+		let used = Weight::from_parts(100_000_000, 0);
+		let treasury_balance = Balances::free_balance(TreasuryAccount::get());
+
+		// The treasury sponsors the tank, so nobody is charged for the gas
+		assert_ok!(MembershipsGasTank::make_tank(&(1, 1), Some(capacity), None));
+		CommunitiesPayForGas::burn_gas(&member, &used, &used);
+		assert_eq!(Balances::free_balance(&community_account), 1_000 * UNITS);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance);
+
+		// The community pays for the gas of the tank it fills
+		assert_ok!(CommunitiesManager::fill_member_gas_tank(
+			RuntimeOrigin::signed(admin),
+			1,
+			capacity
+		));
+		CommunitiesPayForGas::burn_gas(&member, &used, &used);
+		let fee = WeightToFee::weight_to_fee(&used);
+		assert_eq!(Balances::free_balance(&community_account), 1_000 * UNITS - fee);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance + fee);
+	});
+}
+
+#[test]
+fn tanks_their_community_can_not_pay_for_are_emptied() {
+	use crate::{
+		configuration::monetary_stuff::CommunitiesPayForGas, AccountId, Balances, Communities, CommunitiesManager,
+		CommunityMemberships, RuntimeOrigin, TreasuryAccount, UNITS,
+	};
+	use fc_traits_gas_tank::GasBurner;
+	use frame_support::{
+		assert_ok,
+		traits::{fungible::Mutate, nonfungibles_v2::Inspect, nonfungibles_v2::Mutate as _},
+		weights::Weight,
+		BoundedVec,
+	};
+	use sp_runtime::traits::StaticLookup;
+
+	sp_io::TestExternalities::default().execute_with(|| {
+		pallet_communities_manager::GenesisConfig::<Runtime>::default().build();
+
+		let admin = AccountId::new([1; 32]);
+		let member = AccountId::new([2; 32]);
+		assert_ok!(Balances::mint_into(&admin, 1_000 * UNITS));
+		assert_ok!(Balances::mint_into(&TreasuryAccount::get(), 1_000 * UNITS));
+		assert_ok!(CommunitiesManager::register(
+			RuntimeOrigin::root(),
+			1,
+			BoundedVec::truncate_from(b"Community".to_vec()),
+			<Runtime as frame_system::Config>::Lookup::unlookup(admin.clone()),
+			None,
+			None,
+			Default::default(),
+		));
+		assert_ok!(CommunityMemberships::mint_into(
+			&1,
+			&1,
+			&member,
+			&pallet_nfts::ItemConfig::default(),
+			true
+		));
+
+		let community_account = Communities::community_account(&1);
+		assert_ok!(Balances::mint_into(&community_account, 1_000 * UNITS));
+		let used = Weight::from_parts(100_000_000, 0);
+		assert_ok!(CommunitiesManager::fill_member_gas_tank(
+			RuntimeOrigin::signed(admin),
+			1,
+			Weight::from_parts(1_000_000_000, 0)
+		));
+
+		// The community spends its funds before the gas is burned
+		let treasury_balance = Balances::free_balance(TreasuryAccount::get());
+		assert_ok!(Balances::set_balance(&community_account, crate::EXISTENTIAL_DEPOSIT));
+
+		assert_eq!(CommunitiesPayForGas::burn_gas(&member, &used, &used), Weight::zero());
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury_balance);
+		assert_eq!(
+			CommunityMemberships::system_attribute(&1, Some(&1), b"membership_gas"),
+			None
+		);
+		assert_eq!(pallet_communities_manager::GasTankPayers::<Runtime>::get(1, 1), None);
+		assert_eq!(CommunitiesPayForGas::check_available_gas(&member, &used), None);
+	});
+}
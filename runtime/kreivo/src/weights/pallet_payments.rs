@@ -166,15 +166,18 @@ impl<T: frame_system::Config> pallet_payments::WeightInfo for WeightInfo<T> {
 	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(224), added: 2699, mode: `MaxEncodedLen`)
 	/// Storage: `Payments::PaymentParties` (r:0 w:1)
 	/// Proof: `Payments::PaymentParties` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
-	fn request_payment() -> Weight {
+	/// The range of component `q` is `[1, 50]`.
+	fn request_payment(q: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `391`
 		//  Estimated: `8518`
 		// Minimum execution time: 44_338_000 picoseconds.
 		Weight::from_parts(62_431_000, 0)
 			.saturating_add(Weight::from_parts(0, 8518))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(2))
+			// Standard Error: 1_102
+			.saturating_add(Weight::from_parts(1_853, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `Payments::PaymentParties` (r:1 w:0)
 	/// Proof: `Payments::PaymentParties` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
@@ -196,4 +199,109 @@ impl<T: frame_system::Config> pallet_payments::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(6))
 	}
+	/// The range of component `q` is `[1, 50]`.
+	/// The range of component `m` is `[1, 20]`.
+	fn pay_with_milestones(q: u32, m: u32, ) -> Weight {
+		Weight::from_parts(225_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(Weight::from_parts(900_944, 0).saturating_mul(q.into()))
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 41).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	fn pay_with_splits(q: u32, s: u32, ) -> Weight {
+		Weight::from_parts(220_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(Weight::from_parts(900_944, 0).saturating_mul(q.into()))
+			.saturating_add(Weight::from_parts(500_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 33).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	fn release_milestone() -> Weight {
+		Weight::from_parts(290_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8859))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	fn subscribe() -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3997))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn pull_subscription() -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8859))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	fn cancel_subscription() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3997))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn reject_request() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn expire_request() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn grant_discount() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn submit_evidence() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	fn pay_with_hash_lock(q: u32, ) -> Weight {
+		Weight::from_parts(240_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(Weight::from_parts(900_944, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
+	fn claim_payment() -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8859))
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(11))
+	}
+	fn propose_partial_refund() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn accept_partial_refund() -> Weight {
+		Weight::from_parts(310_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8859))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(12))
+	}
+	fn prune_finished_payment() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8517))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	fn apply_default_dispute_result() -> Weight {
+		Weight::from_parts(715_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 8859))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(10))
+	}
 }